@@ -1 +1,9 @@
-
+#[macro_use]
+pub mod node;
+pub mod options;
+pub mod parser;
+pub mod solver;
+pub mod steps;
+pub mod tokenizer;
+pub mod types;
+pub mod visualizer;
@@ -3,59 +3,509 @@
 // 92
 // https://app.diagrams.net/#G14rzgULr5arR4jENATQecRsNx08YcXdhu
 
-#[macro_use]
-mod node;
-mod parser;
-mod types;
-mod tokenizer;
-mod visualizer;
-
+use std::convert::TryFrom;
 use std::env::args;
 
-use crate::types::polynomial::Polynomial;
-use anyhow::{Context, Result};
-use indoc::indoc;
-use node::Branch;
-use parser::Parser;
-use visualizer::render_graph;
+use anyhow::{anyhow, Context, Result};
+use computorv1::node::{Branch, NodeObject};
+use computorv1::options::{Mode, Options};
+use computorv1::parser::Parser;
+use computorv1::solver::{LinearSystem, Relation, Root, Strategy, SymbolicSolution};
+use computorv1::steps::Steps;
+use computorv1::tokenizer::Token;
+use computorv1::types::polynomial::Polynomial;
+use computorv1::types::rational::Rational;
+use computorv1::types::rational_function::RationalFunction;
+use computorv1::visualizer::render_graph;
 
 fn main() -> Result<()> {
-    let input: Vec<String> = args().collect();
+    let options = Options::parse(args().skip(1))?;
     let parser = Parser::new();
+    if options.input.contains(';') {
+        if let Some(arg) = system_conflict(&options) {
+            return Err(anyhow!("{}{}", "SYSTEM_MODE_ERR", arg));
+        }
+        let polynomials = parser
+            .parse_system(&options.input)
+            .context("Unable to parse")?
+            .into_iter()
+            .map(Polynomial::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        let system = LinearSystem::new(&polynomials)?;
+        println!("Augmented matrix:");
+        print!("{}", system.matrix);
+        print!("{}", system.solve()?);
+        return Ok(());
+    }
+    match options.mode {
+        Mode::Divide => return divide(&parser, &options),
+        Mode::Gcd => return common_roots(&parser, &options),
+        Mode::Diff | Mode::Integrate => return calculus(&parser, &options),
+        _ => {}
+    }
     let tree: Branch = parser
-        .parse(input[1].as_str())
+        .parse(options.input.as_str())
         .context("Unable to parse")?
         .unwrap();
 
     render_graph(&tree);
 
-    let mut polynomial = Polynomial::from(tree);
-    polynomial.reduce();
-    let degree = polynomial.degree();
-    polynomial.solve();
+    // An inequality keeps its relation above the difference of both sides.
+    let relation = match &tree.borrow().object {
+        NodeObject::Operator(Token::Operator(operator)) => Relation::try_from(*operator).ok(),
+        _ => None,
+    };
+    let tree = match relation {
+        Some(_) => tree.borrow().left.clone().unwrap(),
+        None => tree,
+    };
+    let symbol = relation.map_or("=".to_owned(), |relation| relation.to_string());
 
-    println!("Reduced form: {polynomial}= 0");
-    println!("Polynomial degree: {degree}");
+    let mut steps = Steps::default();
+    let (lhs, rhs) = {
+        let node = tree.borrow();
+        (node.left.clone().unwrap(), node.right.clone().unwrap())
+    };
+    let side = |branch: Branch| -> Result<String> {
+        Ok(if branch.borrow().is_rational_function() {
+            RationalFunction::try_from(branch)?.to_string()
+        } else {
+            Polynomial::try_from(branch)?
+                .to_string()
+                .trim_end()
+                .to_owned()
+        })
+    };
+    steps.record(format!(
+        "Start from the {}: {} {symbol} {}",
+        if relation.is_some() {
+            "inequality"
+        } else {
+            "equation"
+        },
+        side(lhs)?,
+        side(rhs)?
+    ));
 
-    let output = polynomial.solve();
+    // Dividing by a sum or taking a remainder gives a rational function, whose denominator
+    // is cleared like negative powers below, and whose poles are excluded.
+    let function = if tree.borrow().is_rational_function() {
+        Some(RationalFunction::try_from(tree.clone())?)
+    } else {
+        None
+    };
+    let (denominators, divisors) = match function {
+        Some(_) => (vec![], tree.borrow().divisors()),
+        None => (tree.borrow().denominators(), vec![]),
+    };
+    let mut polynomial = match &function {
+        Some(function) => function.numerator(),
+        None => Polynomial::try_from(tree)?,
+    };
+    steps.record(format!(
+        "Subtract the right-hand side from both sides: {}{symbol} 0",
+        function
+            .as_ref()
+            .map_or(polynomial.to_string(), |f| format!("{f} "))
+    ));
+    let mut poles = vec![];
+    if let Some(function) = function.filter(|function| !function.is_polynomial()) {
+        let denominator = function.denominator();
+        let factor = format!("({})", denominator.to_string().trim_end());
+        let factor = match relation {
+            Some(_) => {
+                polynomial = polynomial.checked_mul(denominator)?;
+                format!("{factor}^2")
+            }
+            None => factor,
+        };
+        steps.record(format!(
+            "Multiply both sides by {factor} to clear the denominator: {polynomial}{symbol} 0"
+        ));
+    }
+    // Cancelling a common factor removes no pole, so the real roots of every divisor as
+    // written are excluded.
+    for divisor in divisors {
+        let divisor = RationalFunction::try_from(divisor)?.numerator();
+        let roots = divisor.solve(&options.strategy)?.roots;
+        poles.extend(roots.into_iter().filter(|root| root.value.is_real()));
+    }
+    poles.sort_by(|a, b| a.value.re.total_cmp(&b.value.re));
+    poles.dedup_by(|a, b| (a.value - b.value).norm() <= 1e-9 * b.value.norm().max(1.0));
+    // Negative powers are cleared by multiplying through, which is only valid where no
+    // denominator is zero.
+    let cleared = polynomial.clear_denominators(relation.is_some());
+    if !cleared.is_empty() {
+        steps.record(format!(
+            "Multiply both sides by {} to clear the denominators: {polynomial}{symbol} 0",
+            cleared
+                .iter()
+                .map(|(identifier, power)| format!("{identifier}^{power}"))
+                .collect::<Vec<_>>()
+                .join(" * ")
+        ));
+    }
+    let exclusions = denominators
+        .iter()
+        .map(|identifier| format!("{identifier} = 0 is excluded, as it makes a denominator zero\n"))
+        .collect::<String>()
+        + &poles
+            .iter()
+            .map(|root| {
+                let value = root
+                    .rational
+                    .map_or(format!("{:.6}", root.value.re), |r| r.to_string());
+                format!(
+                    "{} = {value} is excluded, as it makes a denominator zero\n",
+                    polynomial.identifier()
+                )
+            })
+            .collect::<String>();
+    polynomial.reduce()?;
+    steps.record(format!("Combine like terms: {polynomial}{symbol} 0"));
 
-    if output.len() == 2 {
+    // Other identifiers than the unknown are parameters, so the roots are formulas in them.
+    let identifiers = polynomial.identifiers();
+    let unknown = match &options.solve_for {
+        Some(unknown) => unknown.clone(),
+        None if identifiers.len() > 1 => {
+            return Err(anyhow!(
+                "{}{}",
+                "MULTIPLE_UNKNOWNS_ERR",
+                identifiers.join(", ")
+            ))
+        }
+        None => polynomial.identifier().to_owned(),
+    };
+    if identifiers.iter().any(|identifier| *identifier != unknown) {
+        if !identifiers.contains(&unknown) {
+            return Err(anyhow!("{}{}", "MISSING_UNKNOWN_ERR", unknown));
+        }
+        if relation.is_some() || options.mode != Mode::Solve {
+            return Err(anyhow!("{}{}", "SYMBOLIC_MODE_ERR", symbol));
+        }
+        let coefficients = polynomial.coefficients_in(&unknown)?;
+        let solution = SymbolicSolution::new(&unknown, &coefficients)?;
+        if options.steps {
+            print!("{steps}");
+        }
+        println!("Reduced form: {polynomial}{symbol} 0");
         println!(
-            indoc! {
-                "Discriminant is strictly positive, the two solutions are:
-                {:.6}
-                {:.6}"
-            },
-            output[0], output[1]
+            "Polynomial degree in {unknown}: {}",
+            coefficients.len().saturating_sub(1)
+        );
+        print!("{solution}{exclusions}");
+        return Ok(());
+    }
+
+    // Rational exponents of the unknown become integers in Y = X^(1/q), and every valid root Y
+    // gives X = Y^q.
+    let substitute = if unknown == "Y" { "Z" } else { "Y" };
+    let mut index = 1;
+    if options.substitute {
+        if relation.is_some() || options.mode != Mode::Solve {
+            return Err(anyhow!("{}{}", "SUBSTITUTION_MODE_ERR", symbol));
+        }
+        index = polynomial.substitute_root(&unknown, substitute)?;
+        if index > 1 {
+            steps.record(format!(
+                "Substitute {substitute} = {unknown}^(1/{index}): {polynomial}{symbol} 0"
+            ));
+        }
+    }
+
+    let excluded = denominators.contains(&unknown);
+    let degree = polynomial.degree()?;
+    if options.mode == Mode::Factor {
+        return factor(&polynomial, &symbol, &exclusions);
+    }
+    if let Mode::CountRoots { lower, upper } = options.mode {
+        if relation.is_some() {
+            return Err(anyhow!("{}{}", "UNEXP_RELATION_ERR", symbol));
+        }
+        return count_roots(
+            &polynomial,
+            &unknown,
+            excluded,
+            &exclusions,
+            steps,
+            (lower, upper),
+            &options,
         );
+    }
+
+    let (result, roots) = match relation {
+        Some(relation) => {
+            let mut solution_set = polynomial.solve_inequality(relation, &options.strategy)?;
+            if excluded {
+                solution_set = solution_set.excluding(Root::from(Rational::ZERO));
+            }
+            for pole in &poles {
+                solution_set = solution_set.excluding(*pole);
+            }
+            steps.record(
+                "Find the real roots of the reduced form and test its sign between them".to_owned(),
+            );
+            (format!("{solution_set:.6}"), vec![])
+        }
+        None => {
+            let mut solution = polynomial.solve(&options.strategy)?;
+            if excluded {
+                solution = solution.excluding(Root::from(Rational::ZERO));
+            }
+            for pole in &poles {
+                solution = solution.excluding(*pole);
+            }
+            steps.record_solution(&polynomial, &solution, &options.strategy)?;
+            (
+                format!(
+                    "{:.6}",
+                    solution.notated(options.notation).with_unknown(&unknown)
+                ),
+                solution.roots,
+            )
+        }
+    };
+    if options.steps {
+        print!("{steps}");
+    }
+    if index > 1 {
+        println!("Substitution: {substitute} = {unknown}^(1/{index})");
+    }
+    println!("Reduced form: {polynomial}{symbol} 0");
+    println!("Polynomial degree: {degree}");
+    print!("{result}");
+    if index > 1 && !roots.is_empty() {
+        println!("Back-substitution:");
+        for root in &roots {
+            if !root.value.is_real() {
+                println!(
+                    "{substitute} = {:.6} is rejected, as {unknown} must be real",
+                    root.value
+                );
+            } else if root.value.re < 0.0 && index % 2 == 0 {
+                println!(
+                    "{substitute} = {:.6} is rejected, as {unknown}^(1/{index}) cannot be negative",
+                    root.value.re
+                );
+            } else {
+                println!("{unknown} = {:.6}", root.value.re.powi(index as i32));
+            }
+        }
+    }
+    print!("{exclusions}");
+    if options.verify && !roots.is_empty() {
+        println!("Verification:");
+        for root in &roots {
+            println!(
+                "|P({:.6})| = {:.3e}, Newton-polished value: {:.6}{}",
+                root.value,
+                root.residual,
+                root.polished,
+                if root.verified {
+                    ""
+                } else {
+                    " (exceeds tolerance)"
+                }
+            );
+        }
+        if let Some(root) = roots.iter().find(|root| !root.verified) {
+            return Err(anyhow!("{}{:.6}", "RESIDUAL_TOLERANCE_ERR", root.value));
+        }
+    }
+    Ok(())
+}
+
+/// Mode or flag that a system of equations, which is only ever solved exactly, does not
+/// support.
+fn system_conflict(options: &Options) -> Option<&'static str> {
+    let mode = match options.mode {
+        Mode::Solve => None,
+        Mode::Factor => Some("factor"),
+        Mode::Divide => Some("divide"),
+        Mode::Diff => Some("diff"),
+        Mode::Integrate => Some("integrate"),
+        Mode::Gcd => Some("gcd"),
+        Mode::CountRoots { .. } => Some("count-roots"),
+    };
+    let flags = [
+        (options.steps, "--steps"),
+        (options.verify, "--verify"),
+        (options.solve_for.is_some(), "--solve-for"),
+        (options.substitute, "--substitute"),
+        (options.strategy != Strategy::ClosedForm, "--numeric"),
+    ];
+    mode.or_else(|| {
+        flags
+            .iter()
+            .find(|(given, _)| *given)
+            .map(|(_, flag)| *flag)
+    })
+}
+
+/// Euclidean division of the polynomials of `A / B` or `A % B`.
+fn divide(parser: &Parser, options: &Options) -> Result<()> {
+    let tree = parser
+        .parse_expression(&options.input)
+        .context("Unable to parse")?
+        .context("MISSING_DIVISION_ERR")?;
+    let node = tree.borrow().clone();
+    let (dividend, divisor) = match (&node.object, node.left, node.right) {
+        (NodeObject::Operator(Token::Operator('/' | '%')), Some(left), Some(right)) => (
+            RationalFunction::try_from(left)?,
+            RationalFunction::try_from(right)?,
+        ),
+        _ => return Err(anyhow!("{}{}", "MISSING_DIVISION_ERR", options.input)),
+    };
+    if let Some(function) = [&dividend, &divisor].iter().find(|f| !f.is_polynomial()) {
+        return Err(anyhow!("{}{}", "NON_POLYNOMIAL_ERR", function));
+    }
+    let (quotient, remainder) = dividend.numerator().div_rem(&divisor.numerator())?;
+    println!("Dividend: {}", dividend.numerator().to_string().trim_end());
+    println!("Divisor: {}", divisor.numerator().to_string().trim_end());
+    println!("Quotient: {}", quotient.to_string().trim_end());
+    println!("Remainder: {}", remainder.to_string().trim_end());
+    Ok(())
+}
+
+/// Factored form of a reduced polynomial over the rationals.
+fn factor(polynomial: &Polynomial, symbol: &str, exclusions: &str) -> Result<()> {
+    let factorization = polynomial.factor()?;
+    println!("Reduced form: {polynomial}{symbol} 0");
+    println!("Polynomial degree: {}", polynomial.degree()?);
+    println!("Factored form: {factorization} {symbol} 0");
+    if !factorization.complete {
+        println!("Factorization incomplete: a factor may still be reducible");
+    }
+    print!("{exclusions}");
+    Ok(())
+}
+
+/// Distinct real roots of a reduced polynomial in the closed `interval`, counted with its
+/// Sturm sequence and each refined to the precision of the options.
+fn count_roots(
+    polynomial: &Polynomial,
+    unknown: &str,
+    excluded: bool,
+    exclusions: &str,
+    mut steps: Steps,
+    (lower, upper): (Rational, Rational),
+    options: &Options,
+) -> Result<()> {
+    let width = options.precision.unwrap_or(Rational::new(1, 1_000_000));
+    let sturm = polynomial.sturm_sequence()?;
+    let changes = sturm
+        .sign_changes(lower)
+        .zip(sturm.sign_changes(upper))
+        .context("RATIONAL_OVERFLOW_ERR")?;
+    steps.record(format!(
+        "Count the sign changes of the Sturm sequence at both bounds: V({lower}) = {}, V({upper}) = {}",
+        changes.0, changes.1
+    ));
+    let mut roots = sturm
+        .roots_in(lower, upper, width)
+        .context("RATIONAL_OVERFLOW_ERR")?;
+    if excluded {
+        roots.retain(|root| !(root.lower.is_zero() && root.upper.is_zero()));
+    }
+    if options.steps {
+        print!("{steps}");
+    }
+    println!("Reduced form: {polynomial}= 0");
+    println!("Polynomial degree: {}", polynomial.degree()?);
+    match roots.len() {
+        _ if sturm.polynomials().is_empty() => {
+            println!("Each real number in [{lower}, {upper}] is a root")
+        }
+        0 => println!("There is no real root in [{lower}, {upper}]"),
+        1 => println!("There is 1 distinct real root in [{lower}, {upper}]:"),
+        count => println!("There are {count} distinct real roots in [{lower}, {upper}]:"),
+    }
+    // One more decimal than the width needs, so that both bounds stay distinct.
+    let decimals = (-f64::from(width).log10()).ceil().max(0.0) as usize + 1;
+    let mut coarse = false;
+    for root in roots {
+        if root.lower == root.upper {
+            println!("{unknown} = {root}");
+        } else {
+            coarse |= !matches!(root.upper.checked_sub(root.lower), Some(w) if w <= width);
+            println!("{unknown} ∈ {root:.decimals$}");
+        }
+    }
+    if coarse {
+        println!("Refinement stopped above the precision, where the values would overflow");
+    }
+    print!("{exclusions}");
+    Ok(())
+}
+
+/// Tree of an expression, or of an equation once its right-hand side is subtracted.
+fn expression(parser: &Parser, input: &str) -> Result<Branch> {
+    if input.contains('=') {
+        parser.parse(input)
     } else {
+        parser.parse_expression(input)
+    }
+    .context("Unable to parse")?
+    .context("UNEXP_END_ERR")
+}
+
+/// Polynomial of an expression, or of an equation once its right-hand side is subtracted.
+fn polynomial(parser: &Parser, input: &str) -> Result<Polynomial> {
+    let function = RationalFunction::try_from(expression(parser, input)?)?;
+    if !function.is_polynomial() {
+        return Err(anyhow!("{}{}", "NON_POLYNOMIAL_ERR", function));
+    }
+    Ok(function.numerator())
+}
+
+/// Greatest common divisor and least common multiple of two polynomials, whose common roots
+/// are those of the divisor.
+fn common_roots(parser: &Parser, options: &Options) -> Result<()> {
+    let first = polynomial(parser, &options.input)?;
+    let second = polynomial(
+        parser,
+        options.second.as_deref().context("MISSING_INPUT_ERR")?,
+    )?;
+    let divisor = first.gcd(&second)?;
+    let multiple = first.lcm(&second)?;
+    println!("First: {}", first.to_string().trim_end());
+    println!("Second: {}", second.to_string().trim_end());
+    println!("GCD: {}", divisor.to_string().trim_end());
+    println!("LCM: {}", multiple.to_string().trim_end());
+    if divisor.is_empty() {
+        println!("Each real number is a common root");
+        return Ok(());
+    }
+    let roots = divisor.solve(&options.strategy)?.roots;
+    match roots.len() {
+        0 => println!("There is no common root"),
+        1 => println!("The common root is:"),
+        _ => println!("The common roots are:"),
+    }
+    for root in roots {
+        println!("{} = {root:.6}", divisor.identifier());
+    }
+    Ok(())
+}
+
+/// Derivative of an expression, differentiating its tree with respect to its unknown, or
+/// antiderivative of a polynomial.
+fn calculus(parser: &Parser, options: &Options) -> Result<()> {
+    let tree = expression(parser, &options.input)?;
+    let function = RationalFunction::try_from(tree.clone())?;
+    println!("Expression: {function}");
+    if options.mode == Mode::Integrate {
+        if !function.is_polynomial() {
+            return Err(anyhow!("{}{}", "NON_POLYNOMIAL_ERR", function));
+        }
         println!(
-            indoc! {
-                "The solution is:
-                {:.6}"
-            },
-            output[0]
+            "Antiderivative: {}+ C",
+            function.numerator().integral(Rational::ZERO)?
         );
+        return Ok(());
     }
+    let derivative = tree.borrow().derivative(function.identifier())?;
+    println!("Derivative: {}", RationalFunction::try_from(derivative)?);
     Ok(())
 }
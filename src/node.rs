@@ -1,11 +1,14 @@
 use dot::{LabelText, Style};
 
 use crate::tokenizer::Token;
+use crate::types::rational::Rational;
 use crate::types::Type;
+use anyhow::{anyhow, Context, Result};
 use derive_more::Display;
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::cell::{Ref, RefMut};
+use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -50,6 +53,7 @@ impl<'a> dot::Labeller<'a, (usize, NodeObject), Edge> for Edges {
                 '+' | '-' => "#F1E2A7",
                 '*' | '/' | '%' => "#E9D172",
                 '^' => "#E1C03D",
+                '<' | '>' | '≤' | '≥' => "#D9AF08",
                 _ => unimplemented!(),
             },
             NodeObject::Operator(Token::Number(_)) => "#00A0B0",
@@ -65,9 +69,9 @@ impl<'a> dot::Labeller<'a, (usize, NodeObject), Edge> for Edges {
 
 impl<'a> dot::GraphWalk<'a, (usize, NodeObject), Edge> for Edges {
     fn nodes(&self) -> dot::Nodes<'a, (usize, NodeObject)> {
-        let &Edges(ref v) = self;
+        let Edges(v) = self;
         let mut nodes = Vec::with_capacity(v.len());
-        for Edge((source_id, source_token), (target_id, target_token)) in v.into_iter() {
+        for Edge((source_id, source_token), (target_id, target_token)) in v.iter() {
             nodes.push((*source_id, source_token.clone()));
             nodes.push((*target_id, target_token.clone()));
         }
@@ -75,7 +79,7 @@ impl<'a> dot::GraphWalk<'a, (usize, NodeObject), Edge> for Edges {
     }
 
     fn edges(&'a self) -> dot::Edges<'a, Edge> {
-        let &Edges(ref edges) = self;
+        let Edges(edges) = self;
         Cow::Borrowed(&edges[..])
     }
 
@@ -94,9 +98,9 @@ pub enum NodeObject {
     Operand(Rc<dyn Type>),
 }
 
-impl Into<Token> for NodeObject {
-    fn into(self) -> Token {
-        match self {
+impl From<NodeObject> for Token {
+    fn from(object: NodeObject) -> Self {
+        match object {
             NodeObject::Operator(token) => token,
             NodeObject::Operand(_) => panic!(),
         }
@@ -160,6 +164,153 @@ impl Node {
         }
         ret
     }
+
+    /// Identifiers in a denominator, either divided by or raised to a negative power, in
+    /// alphabetical order. Only products and powers of identifiers count as denominators,
+    /// which are zero exactly where one of their identifiers is.
+    pub fn denominators(&self) -> Vec<String> {
+        let identifiers = |branch: &Branch| -> Option<Vec<String>> {
+            let mut identifiers = vec![];
+            for branch in branch.borrow().clone() {
+                match &branch.borrow().object {
+                    NodeObject::Operand(operand) => {
+                        identifiers.extend(operand.to_term().identifier)
+                    }
+                    NodeObject::Operator(Token::Operator('*' | '^') | Token::Number(_)) => {}
+                    _ => return None,
+                }
+            }
+            Some(identifiers)
+        };
+        let mut denominators = self
+            .divisors()
+            .iter()
+            .filter_map(identifiers)
+            .flatten()
+            .collect::<Vec<_>>();
+        denominators.sort();
+        denominators.dedup();
+        denominators
+    }
+
+    /// Every expression divided by or raised to a negative power, before any cancellation.
+    pub fn divisors(&self) -> Vec<Branch> {
+        let mut divisors = vec![];
+        for branch in self.clone() {
+            let node = branch.borrow();
+            let divisor = match (&node.object, &node.left, &node.right) {
+                (NodeObject::Operator(Token::Operator('/')), _, Some(right)) => Some(right),
+                (NodeObject::Operator(Token::Operator('^')), Some(left), Some(right))
+                    if matches!(
+                        right.borrow().object,
+                        NodeObject::Operator(Token::Number(exponent)) if exponent < 0.0
+                    ) =>
+                {
+                    Some(left)
+                }
+                _ => None,
+            };
+            divisors.extend(divisor.cloned());
+        }
+        divisors
+    }
+
+    /// Derivative with respect to `identifier` by the sum, product, quotient and power rules,
+    /// every other identifier being a constant. The result is not simplified.
+    pub fn derivative(&self, identifier: &str) -> Result<Branch> {
+        let operation = |operator: char, left: Branch, right: Branch| {
+            Branch::new(Node::new(
+                (&Token::Operator(operator)).into(),
+                Some(left),
+                Some(right),
+            ))
+        };
+        let constant =
+            |value: Rational| Branch::new(Node::new(value.into_node_object(), None, None));
+        let (left, right) = (self.left.clone(), self.right.clone());
+        let operands = || -> Result<(Branch, Branch)> {
+            Ok((
+                left.clone().context("UNEXP_END_ERR")?,
+                right.clone().context("UNEXP_END_ERR")?,
+            ))
+        };
+        let derivatives = |u: &Branch, v: &Branch| -> Result<(Branch, Branch)> {
+            Ok((
+                u.borrow().derivative(identifier)?,
+                v.borrow().derivative(identifier)?,
+            ))
+        };
+        match &self.object {
+            NodeObject::Operand(operand) => {
+                if operand.to_term().identifier.as_deref() == Some(identifier) {
+                    Ok(constant(Rational::ONE))
+                } else {
+                    Ok(constant(Rational::ZERO))
+                }
+            }
+            NodeObject::Operator(Token::Operator(operator @ ('+' | '-'))) => {
+                let (u, v) = operands()?;
+                let (du, dv) = derivatives(&u, &v)?;
+                Ok(operation(*operator, du, dv))
+            }
+            NodeObject::Operator(Token::Operator('*')) => {
+                let (u, v) = operands()?;
+                let (du, dv) = derivatives(&u, &v)?;
+                Ok(operation('+', operation('*', du, v), operation('*', u, dv)))
+            }
+            NodeObject::Operator(Token::Operator('/')) => {
+                let (u, v) = operands()?;
+                let (du, dv) = derivatives(&u, &v)?;
+                let numerator =
+                    operation('-', operation('*', du, v.clone()), operation('*', u, dv));
+                Ok(operation('/', numerator, operation('*', v.clone(), v)))
+            }
+            NodeObject::Operator(Token::Operator('^')) => {
+                let (u, exponent) = operands()?;
+                let n = match &exponent.borrow().object {
+                    NodeObject::Operator(Token::Number(n)) => *n,
+                    object => return Err(anyhow!("{}{}", "UNEXP_EXPONENT_ERR", object)),
+                };
+                let power = Branch::new(Node::new((&Token::Number(n - 1.0)).into(), None, None));
+                let du = u.borrow().derivative(identifier)?;
+                let factor = constant(Rational::try_from(n)?);
+                Ok(operation(
+                    '*',
+                    operation('*', factor, operation('^', u, power)),
+                    du,
+                ))
+            }
+            object => Err(anyhow!("{}{}", "NON_DIFFERENTIABLE_ERR", object)),
+        }
+    }
+
+    /// Whether the expression takes a remainder or divides by a sum, directly or through a
+    /// negative power, so that it is a rational function rather than a polynomial.
+    pub fn is_rational_function(&self) -> bool {
+        let is_sum = |branch: &Branch| {
+            branch.borrow().clone().into_iter().any(|branch| {
+                matches!(
+                    branch.borrow().object,
+                    NodeObject::Operator(Token::Operator('+' | '-'))
+                )
+            })
+        };
+        self.clone().into_iter().any(|branch| {
+            let node = branch.borrow();
+            match (&node.object, &node.left, &node.right) {
+                (NodeObject::Operator(Token::Operator('%')), _, _) => true,
+                (NodeObject::Operator(Token::Operator('/')), _, Some(right)) => is_sum(right),
+                (NodeObject::Operator(Token::Operator('^')), Some(left), Some(right)) => {
+                    is_sum(left)
+                        && matches!(
+                            right.borrow().object,
+                            NodeObject::Operator(Token::Number(exponent)) if exponent < 0.0
+                        )
+                }
+                _ => false,
+            }
+        })
+    }
 }
 
 impl IntoIterator for Node {
@@ -174,7 +325,7 @@ impl IntoIterator for Node {
             let iter = left.borrow_mut().clone().into_iter();
             Rc::new(RefCell::new(right.map_or(
                 Box::new(iter.clone()) as Box<dyn Iterator<Item = Branch>>,
-                |mut right| Box::new(iter.chain(right.borrow_mut().clone().into_iter())),
+                |mut right| Box::new(iter.chain(right.borrow_mut().clone())),
             )))
         });
 
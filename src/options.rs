@@ -0,0 +1,238 @@
+use crate::solver::{Notation, Numeric, Strategy};
+use crate::types::rational::Rational;
+use anyhow::{anyhow, Context, Result};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// What to do with the equation.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Mode {
+    #[default]
+    Solve,
+    /// Print the factorization of the reduced polynomial instead of its roots.
+    Factor,
+    /// Divide the polynomials of an expression `A / B` or `A % B`, printing the quotient and
+    /// the remainder.
+    Divide,
+    /// Differentiate the expression.
+    Diff,
+    /// Integrate the polynomial, with an arbitrary constant.
+    Integrate,
+    /// Print the greatest common divisor and least common multiple of two polynomials, and
+    /// their common roots.
+    Gcd,
+    /// Count the distinct real roots in the closed interval and isolate each of them.
+    CountRoots { lower: Rational, upper: Rational },
+}
+
+/// Command line configuration. The mode and flags may appear before or after the equation.
+#[derive(Debug, Default)]
+pub struct Options {
+    pub input: String,
+    /// Second polynomial of the `gcd` mode.
+    pub second: Option<String>,
+    pub mode: Mode,
+    pub strategy: Strategy,
+    pub notation: Notation,
+    /// Print a numbered derivation before the result.
+    pub steps: bool,
+    /// Unknown to solve for, every other identifier being a symbolic parameter.
+    pub solve_for: Option<String>,
+    /// Turn rational exponents of the unknown into integers by substituting `Y = X^(1/q)`.
+    pub substitute: bool,
+    /// Check every root against the equation, failing when a residual exceeds tolerance.
+    pub verify: bool,
+    /// Largest width of the intervals isolating the roots in `count-roots` mode.
+    pub precision: Option<Rational>,
+}
+
+impl Options {
+    pub fn parse<I>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut inputs = vec![];
+        let mut mode = Mode::Solve;
+        let mut numeric = None;
+        let mut notation = Notation::Decimal;
+        let mut steps = false;
+        let mut solve_for = None;
+        let mut verify = false;
+        let mut substitute = false;
+        let mut precision = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "factor" => mode = Mode::Factor,
+                "divide" => mode = Mode::Divide,
+                "gcd" => mode = Mode::Gcd,
+                "diff" => mode = Mode::Diff,
+                "integrate" => mode = Mode::Integrate,
+                "count-roots" => mode = interval(&mut args)?,
+                "--fraction" => notation = Notation::Fraction,
+                "--exact" => notation = Notation::Exact,
+                "--steps" => steps = true,
+                "--verify" => verify = true,
+                "--substitute" => substitute = true,
+                "--solve-for" => solve_for = Some(value(&mut args, &arg)?),
+                "--precision" => {
+                    let width: f64 = value(&mut args, &arg)?;
+                    match Rational::try_from(width) {
+                        Ok(width) if !width.is_zero() && !width.is_negative() => {
+                            precision = Some(width)
+                        }
+                        _ => return Err(anyhow!("{}{}", "INVALID_VALUE_ERR", arg)),
+                    }
+                }
+                "--numeric" => {
+                    numeric.get_or_insert_with(Numeric::default);
+                }
+                // Tuning the iteration only makes sense for the numeric strategy, so imply it.
+                "--tolerance" => {
+                    numeric.get_or_insert_with(Numeric::default).tolerance = value(&mut args, &arg)?
+                }
+                "--max-iterations" => {
+                    numeric.get_or_insert_with(Numeric::default).max_iterations =
+                        value(&mut args, &arg)?
+                }
+                flag if flag.starts_with("--") => {
+                    return Err(anyhow!("{}{}", "UNKNOWN_OPTION_ERR", flag))
+                }
+                _ => inputs.push(arg),
+            }
+        }
+        // Only the gcd mode takes a second polynomial.
+        let mut inputs = inputs.into_iter();
+        let input = inputs.next().context("MISSING_INPUT_ERR")?;
+        let second = match mode {
+            Mode::Gcd => Some(inputs.next().context("MISSING_INPUT_ERR")?),
+            _ => None,
+        };
+        if let Some(arg) = inputs.next() {
+            return Err(anyhow!("{}{}", "UNEXP_ARG_ERR", arg));
+        }
+        Ok(Options {
+            input,
+            second,
+            mode,
+            strategy: numeric.map_or(Strategy::ClosedForm, Strategy::Numeric),
+            notation,
+            steps,
+            solve_for,
+            substitute,
+            verify,
+            precision,
+        })
+    }
+}
+
+fn value<I, T>(args: &mut I, flag: &str) -> Result<T>
+where
+    I: Iterator<Item = String>,
+    T: FromStr,
+{
+    args.next()
+        .and_then(|value| value.parse().ok())
+        .with_context(|| format!("{}{}", "INVALID_VALUE_ERR", flag))
+}
+
+/// Reads the interval `[a,b]` following `count-roots`, whose bounds are integers, decimals or
+/// fractions `p/q`.
+fn interval<I>(args: &mut I) -> Result<Mode>
+where
+    I: Iterator<Item = String>,
+{
+    let arg = args.next().context("MISSING_INTERVAL_ERR")?;
+    let bound = |bound: &str| -> Option<Rational> {
+        let bound = bound.trim();
+        match bound.split_once('/') {
+            Some((numerator, denominator)) => Rational::checked_new(
+                numerator.trim().parse().ok()?,
+                denominator.trim().parse().ok()?,
+            ),
+            None => Rational::try_from(bound.parse::<f64>().ok()?).ok(),
+        }
+    };
+    let (lower, upper) = arg
+        .strip_prefix('[')
+        .and_then(|arg| arg.strip_suffix(']'))
+        .and_then(|arg| arg.split_once(','))
+        .and_then(|(lower, upper)| Some((bound(lower)?, bound(upper)?)))
+        .filter(|(lower, upper)| lower <= upper)
+        .with_context(|| format!("{}{}", "INVALID_INTERVAL_ERR", arg))?;
+    Ok(Mode::CountRoots { lower, upper })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_strategy() {
+        assert_eq!(Strategy::ClosedForm, parse(&["X = 1"]).unwrap().strategy);
+        assert_eq!(
+            Strategy::Numeric(Numeric::default()),
+            parse(&["--numeric", "X = 1"]).unwrap().strategy
+        );
+        assert_eq!(
+            Strategy::Numeric(Numeric {
+                tolerance: 1e-6,
+                max_iterations: 20
+            }),
+            parse(&["X = 1", "--tolerance", "1e-6", "--max-iterations", "20"])
+                .unwrap()
+                .strategy
+        );
+    }
+
+    #[test]
+    fn test_flags() {
+        let options = parse(&["--steps", "X = 1", "--exact"]).unwrap();
+        assert!(options.steps);
+        assert_eq!(Notation::Exact, options.notation);
+        assert!(!parse(&["X = 1"]).unwrap().steps);
+        assert!(parse(&["X = 1", "--verify"]).unwrap().verify);
+        assert!(parse(&["--substitute", "X^0.5 = 1"]).unwrap().substitute);
+        assert_eq!(Mode::Factor, parse(&["factor", "X = 1"]).unwrap().mode);
+        assert_eq!(Mode::Divide, parse(&["divide", "X / 2"]).unwrap().mode);
+        assert_eq!(Mode::Diff, parse(&["diff", "X^2"]).unwrap().mode);
+        assert_eq!(Mode::Integrate, parse(&["X^2", "integrate"]).unwrap().mode);
+        let options = parse(&["X^2 - 1", "gcd", "X + 1"]).unwrap();
+        assert_eq!(Mode::Gcd, options.mode);
+        assert_eq!(Some("X + 1".to_owned()), options.second);
+        assert!(parse(&["gcd", "X^2 - 1"]).is_err());
+        assert!(parse(&["X = 1", "X = 2"]).is_err());
+        assert_eq!(Mode::Solve, parse(&["X = 1"]).unwrap().mode);
+        assert_eq!(
+            Mode::CountRoots {
+                lower: Rational::from(-2),
+                upper: Rational::new(1, 2)
+            },
+            parse(&["count-roots", "[-2, 1/2]", "X = 1"]).unwrap().mode
+        );
+        assert_eq!(
+            Some(Rational::new(1, 1000)),
+            parse(&["--precision", "1e-3", "X = 1"]).unwrap().precision
+        );
+        assert_eq!(
+            Some("Y".to_owned()),
+            parse(&["--solve-for", "Y", "a * Y = 1"]).unwrap().solve_for
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--unknown", "X = 1"]).is_err());
+        assert!(parse(&["X = 1", "--tolerance"]).is_err());
+        assert!(parse(&["X = 1", "--max-iterations", "many"]).is_err());
+        assert!(parse(&["X = 1", "X = 2"]).is_err());
+        assert!(parse(&["count-roots", "X = 1"]).is_err());
+        assert!(parse(&["count-roots", "[2,1]", "X = 1"]).is_err());
+        assert!(parse(&["--precision", "0", "X = 1"]).is_err());
+    }
+}
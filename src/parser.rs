@@ -1,10 +1,11 @@
-use crate::node::{Branch, Node};
+use crate::node::{Branch, Node, NodeObject};
 use crate::tokenizer::{Token, Token::*, Tokenizer};
 use crate::types::Type;
 use crate::types::function::Function;
 use crate::types::rational::Rational;
 use crate::types::variable::Variable;
 use anyhow::{anyhow, Context, Result};
+use std::convert::TryFrom;
 use std::iter::Peekable;
 
 #[derive(Default)]
@@ -28,6 +29,17 @@ impl<'a> Parser {
                     Some(t) => Err(anyhow!("{}{:?}", "UNEXP_TOKEN_ERR", t)),
                 }
             }
+            // An inequality keeps its relation as the root, above the same difference.
+            Some(Operator(relation @ ('<' | '>' | '≤' | '≥'))) => {
+                let rhs = self.expression(tokenlist);
+                match tokenlist.next() {
+                    None => Ok(node!(
+                        (&Operator(*relation)).into(),
+                        node!((&Operator('-')).into(), lhs?, rhs?)
+                    )),
+                    Some(t) => Err(anyhow!("{}{:?}", "UNEXP_TOKEN_ERR", t)),
+                }
+            }
             _ => Err(anyhow!("MISSING_IMPLICATOR_ERR")),
         }
     }
@@ -73,17 +85,46 @@ impl<'a> Parser {
                 let parent = tokens.next().context("INSERT ERROR")?;
                 // Keep this: let rhs = self.factor(tokens);
                 // This is a temporary solution in order to cast the exponent as a Token rather than a Rational.
-                let temp = if let Some(Number(number)) = tokens.next() {
-                    node!((&Number(*number)).into(), None, None)
-                } else {
-                    panic!()
-                };
+                let temp = node!((&Number(self.exponent(tokens)?)).into(), None, None);
                 Ok(node!(parent.into(), lhs?, temp))
             }
             _ => lhs,
         }
     }
 
+    /// A signed number, as in X^-1, or a signed fraction within parentheses, as in X^(1/3).
+    fn exponent<I>(&self, tokens: &mut Peekable<I>) -> Result<f64>
+    where
+        I: Iterator<Item = &'a Token>,
+    {
+        let parenthesized = matches!(tokens.peek(), Some(Parenthesis('(')));
+        if parenthesized {
+            tokens.next();
+        }
+        let negative = matches!(tokens.peek(), Some(Operator('-')));
+        if negative || matches!(tokens.peek(), Some(Operator('+'))) {
+            tokens.next();
+        }
+        let mut exponent = match tokens.next() {
+            Some(Number(number)) => *number,
+            t => return Err(anyhow!("{}{:?}", "UNEXP_EXPONENT_ERR", t)),
+        };
+        if parenthesized {
+            if let Some(Operator('/')) = tokens.peek() {
+                tokens.next();
+                match tokens.next() {
+                    Some(Number(denominator)) if *denominator != 0.0 => exponent /= denominator,
+                    t => return Err(anyhow!("{}{:?}", "UNEXP_EXPONENT_ERR", t)),
+                }
+            }
+            match tokens.next() {
+                Some(Parenthesis(')')) => {}
+                _ => return Err(anyhow!("MISSING_PAREN_ERR")),
+            }
+        }
+        Ok(if negative { -exponent } else { exponent })
+    }
+
     fn primary<I>(&self, tokenlist: &mut Peekable<I>) -> Result<Option<Branch>>
     where
         I: Iterator<Item = &'a Token>,
@@ -113,7 +154,9 @@ impl<'a> Parser {
                     Ok(node!(Variable(identifier.clone()).into_node_object()))
                 }
             },
-            Some(Number(number)) => Ok(node!(Rational(*number).into_node_object())),
+            Some(Number(number)) => Ok(node!(Rational::try_from(*number)?.into_node_object())),
+            // Complex coefficients are not supported, only complex roots.
+            Some(Imaginary) => Err(anyhow!("{}{:?}", "UNEXP_TOKEN_ERR", Imaginary)),
             _ => Err(anyhow!("UNEXP_END_ERR")),
         }
     }
@@ -124,11 +167,42 @@ impl<'a> Parser {
         self.equation(&mut tokenlist.iter().peekable())
             .context("SYNTAX_ERR")
     }
+
+    /// Parses an expression without an equals sign or a relation.
+    pub fn parse_expression(&self, input: &str) -> Result<Option<Branch>> {
+        let tokenizer = Tokenizer::new();
+        let tokenlist = tokenizer.tokenize(input).context("TOKENIZATION_ERR")?;
+        let mut tokens = tokenlist.iter().peekable();
+        let expression = self.expression(&mut tokens).context("SYNTAX_ERR")?;
+        match tokens.next() {
+            None => Ok(expression),
+            Some(t) => Err(anyhow!("{}{:?}", "UNEXP_TOKEN_ERR", t)).context("SYNTAX_ERR"),
+        }
+    }
+
+    /// Parses equations separated by semicolons.
+    pub fn parse_system(&self, input: &str) -> Result<Vec<Branch>> {
+        let tokenizer = Tokenizer::new();
+        let tokenlist = tokenizer.tokenize(input).context("TOKENIZATION_ERR")?;
+        tokenlist
+            .split(|token| *token == Semicolon)
+            .map(|equation| {
+                let branch = self
+                    .equation(&mut equation.iter().peekable())
+                    .context("SYNTAX_ERR")?
+                    .context("SYNTAX_ERR")?;
+                match &branch.borrow().object {
+                    NodeObject::Operator(Operator('-')) => {}
+                    object => return Err(anyhow!("{}{}", "UNEXP_RELATION_ERR", object)),
+                }
+                Ok(branch)
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::node::NodeObject;
     use super::*;
 
     fn get_branch(input: &str) -> Node {
@@ -138,25 +212,64 @@ mod tests {
 
     #[test]
     fn test_variable() {
-        let mut branches = get_branch("identifier + function(identifier) = 0").into_iter();
+        let mut branches = get_branch("identifier + function(identifier) = 0")
+            .into_iter()
+            .filter(|branch| matches!(branch.borrow().object, NodeObject::Operand(_)));
 
         if let Some(branch) = branches.next() {
             if let NodeObject::Operand(variable) = branch.borrow().clone().object {
-                assert_eq!(Variable("identifier".to_owned()).into_term(), variable.into_term());
+                assert_eq!(Variable("identifier".to_owned()).to_term(), variable.to_term());
             }
         } else {
             panic!()
         }
         if let Some(branch) = branches.next() {
             if let NodeObject::Operand(function) = branch.borrow().clone().object {
-                assert_eq!(Function { identifier: "identifier".to_owned(), arg: "identifier".to_owned() }.into_term(), function.into_term());
+                assert_eq!(Function { identifier: "function".to_owned(), arg: "identifier".to_owned() }.to_term(), function.to_term());
             }
         } else {
             panic!()
         }
-        // assert!(branches.next().is_some());
-        // assert!(branches.next().is_some());
+        assert!(branches.next().is_some());
         assert!(branches.next().is_none());
     }
 
+    #[test]
+    fn test_system() {
+        let parser = Parser::new();
+        assert_eq!(2, parser.parse_system("X + Y = 3; X - Y = 1").unwrap().len());
+        assert!(parser.parse_system("X + Y = 3;").is_err());
+        assert!(parser.parse_system("X + Y = 3; X < 1").is_err());
+        assert!(parser.parse("X + Y = 3; X - Y = 1").is_err());
+    }
+
+    #[test]
+    fn test_inequality() {
+        let node = get_branch("X^2 >= 4");
+        assert!(matches!(node.object, NodeObject::Operator(Operator('≥'))));
+        let difference = node.left.unwrap();
+        assert!(matches!(difference.borrow().object, NodeObject::Operator(Operator('-'))));
+        assert!(node.right.is_none());
+
+        let parser = Parser::new();
+        assert!(parser.parse("X < 1 < 2").is_err());
+        assert!(parser.parse("X + 1").is_err());
+    }
+
+    #[test]
+    fn test_negative_exponent() {
+        let node = get_branch("X^-2 = 1");
+        let power = node.left.unwrap();
+        let exponent = power.borrow().right.clone().unwrap();
+        assert!(matches!(exponent.borrow().object.clone().into(), Number(n) if n == -2.0));
+
+        let parser = Parser::new();
+        assert!(parser.parse("X^+2 = 1").is_ok());
+        assert!(parser.parse("X^Y = 1").is_err());
+        assert!(parser.parse("X^ = 1").is_err());
+        assert!(parser.parse("X^(-1/3) = 1").is_ok());
+        assert!(parser.parse("X^(1/0) = 1").is_err());
+        assert!(parser.parse("X^(1/3 = 1").is_err());
+    }
+
 }
\ No newline at end of file
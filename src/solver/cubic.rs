@@ -0,0 +1,118 @@
+use crate::types::complex::Complex;
+use itertools::Itertools;
+use std::f64::consts::PI;
+
+/// Relative tolerance under which the discriminant of the depressed cubic is considered zero.
+const EPSILON: f64 = 1e-10;
+
+/// Relative residual under which a suspected double root is confirmed, a few hundred times the
+/// rounding error of evaluating the cubic.
+const RESIDUAL: f64 = 1e3 * f64::EPSILON;
+
+/// Whether `t^3 + p * t + q` really has the double root `-3q / 2p` that a vanishing
+/// discriminant suggests. Two simple roots close to each other leave a residual there which
+/// rounding alone cannot explain.
+fn has_double_root(p: f64, q: f64) -> bool {
+    if p == 0.0 {
+        return q == 0.0;
+    }
+    let t = -3.0 * q / (2.0 * p);
+    let residual = t.powi(3) + p * t + q;
+    residual.abs() <= RESIDUAL * (t.abs().powi(3) + (p * t).abs() + q.abs())
+}
+
+/// Solves `a * x^3 + b * x^2 + c * x + d = 0`, listing a repeated root once per multiplicity.
+pub fn cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<Complex> {
+    // Substituting x = t - b / 3a yields the depressed cubic t^3 + p * t + q = 0.
+    let shift = -b / (3.0 * a);
+    let p = (3.0 * a * c - b * b) / (3.0 * a * a);
+    let q = (2.0 * b.powi(3) - 9.0 * a * b * c + 27.0 * a * a * d) / (27.0 * a.powi(3));
+
+    let discriminant = -(4.0 * p.powi(3) + 27.0 * q * q);
+    let scale = (4.0 * p.powi(3)).abs().max(27.0 * q * q);
+
+    let suspected = discriminant.abs() <= EPSILON * scale || scale == 0.0;
+    let roots = if suspected && has_double_root(p, q) {
+        if p == 0.0 {
+            vec![Complex::from(0.0); 3]
+        } else {
+            let double = -3.0 * q / (2.0 * p);
+            vec![
+                Complex::from(double),
+                Complex::from(double),
+                Complex::from(3.0 * q / p),
+            ]
+        }
+    } else if discriminant > 0.0 {
+        // Casus irreducibilis: Cardano's formula would need complex cube roots, so use the
+        // trigonometric form for the three distinct real roots instead.
+        let amplitude = 2.0 * (-p / 3.0).sqrt();
+        let angle = (3.0 * q / (p * amplitude)).clamp(-1.0, 1.0).acos() / 3.0;
+        (0..3)
+            .map(|k| amplitude * (angle - 2.0 * PI * k as f64 / 3.0).cos())
+            .sorted_by(|a, b| a.partial_cmp(b).unwrap())
+            .map(Complex::from)
+            .collect()
+    } else {
+        let root = (q * q / 4.0 + p.powi(3) / 27.0).sqrt();
+        let u = (-q / 2.0 + root).cbrt();
+        let v = (-q / 2.0 - root).cbrt();
+        let re = -(u + v) / 2.0;
+        let im = (3.0_f64.sqrt() / 2.0 * (u - v)).abs();
+        vec![
+            Complex::from(u + v),
+            Complex::new(re, -im),
+            Complex::new(re, im),
+        ]
+    };
+    roots
+        .into_iter()
+        .map(|root| root + Complex::from(shift))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roots(expected: &[Complex], roots: Vec<Complex>) {
+        assert_eq!(expected.len(), roots.len());
+        for (expected, root) in expected.iter().zip(roots) {
+            assert!((*expected - root).norm() < 1e-9, "{} != {}", expected, root);
+        }
+    }
+
+    #[test]
+    fn test_three_real_roots() {
+        let expected = [1.0, 2.0, 3.0].map(Complex::from);
+        assert_roots(&expected, cubic(1.0, -6.0, 11.0, -6.0));
+    }
+
+    #[test]
+    fn test_repeated_roots() {
+        let expected = [1.0, 1.0, 2.0].map(Complex::from);
+        assert_roots(&expected, cubic(1.0, -4.0, 5.0, -2.0));
+        assert_roots(&[Complex::from(-2.0); 3], cubic(2.0, 12.0, 24.0, 16.0));
+    }
+
+    #[test]
+    fn test_close_roots() {
+        // A vanishing discriminant alone would merge 1 and 1.00001 into a double root.
+        let expected = [-2.00001, 1.0, 1.00001].map(Complex::from);
+        let roots = cubic(1.0, 0.0, -3.0000300001, 2.0000300001);
+        assert_eq!(expected.len(), roots.len());
+        for (expected, root) in expected.iter().zip(roots) {
+            assert!((*expected - root).norm() < 1e-7, "{} != {}", expected, root);
+        }
+    }
+
+    #[test]
+    fn test_complex_roots() {
+        let expected = [
+            Complex::from(2.0),
+            Complex::new(-1.0, -3.0_f64.sqrt()),
+            Complex::new(-1.0, 3.0_f64.sqrt()),
+        ];
+        assert_roots(&expected, cubic(1.0, 0.0, 0.0, -8.0));
+    }
+}
@@ -0,0 +1,321 @@
+use super::Root;
+use anyhow::{anyhow, Error, Result};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// Comparison of a polynomial with zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Relation {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+impl Relation {
+    pub fn holds(&self, value: f64) -> bool {
+        match self {
+            Relation::Less => value < 0.0,
+            Relation::LessEqual => value <= 0.0,
+            Relation::Greater => value > 0.0,
+            Relation::GreaterEqual => value >= 0.0,
+        }
+    }
+
+    pub fn is_strict(&self) -> bool {
+        matches!(self, Relation::Less | Relation::Greater)
+    }
+}
+
+impl TryFrom<char> for Relation {
+    type Error = Error;
+
+    /// Maps the operator characters produced by the tokenizer, where `≤` and `≥` stand for
+    /// `<=` and `>=`.
+    fn try_from(operator: char) -> Result<Self> {
+        match operator {
+            '<' => Ok(Relation::Less),
+            '≤' => Ok(Relation::LessEqual),
+            '>' => Ok(Relation::Greater),
+            '≥' => Ok(Relation::GreaterEqual),
+            _ => Err(anyhow!("{}{}", "UNEXP_RELATION_ERR", operator)),
+        }
+    }
+}
+
+impl Display for Relation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Relation::Less => write!(f, "<"),
+            Relation::LessEqual => write!(f, "<="),
+            Relation::Greater => write!(f, ">"),
+            Relation::GreaterEqual => write!(f, ">="),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endpoint {
+    Infinite,
+    Open(Root),
+    Closed(Root),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    pub lower: Endpoint,
+    pub upper: Endpoint,
+}
+
+/// Union of disjoint intervals, in increasing order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolutionSet(pub Vec<Interval>);
+
+impl SolutionSet {
+    /// Solves `p(X) relation 0` from the distinct real roots of `p`, in increasing order, and
+    /// its evaluation. The sign of `p` is constant between consecutive roots, so testing one
+    /// point of each gap decides it, while the roots themselves only satisfy non-strict
+    /// relations.
+    pub fn new(relation: Relation, roots: &[Root], evaluate: impl Fn(f64) -> f64) -> Self {
+        let values = roots.iter().map(|root| root.value.re).collect::<Vec<_>>();
+        let gap_holds = |i: usize| {
+            let point = match (i.checked_sub(1).map(|i| values[i]), values.get(i)) {
+                (None, None) => 0.0,
+                (None, Some(upper)) => upper - 1.0,
+                (Some(lower), None) => lower + 1.0,
+                (Some(lower), Some(upper)) => (lower + upper) / 2.0,
+            };
+            relation.holds(evaluate(point))
+        };
+
+        // Walk the alternating sequence gap, root, gap, ..., root, gap and merge every run of
+        // satisfied pieces into one interval.
+        let mut intervals = vec![];
+        let mut lower = None;
+        for (i, root) in roots.iter().enumerate() {
+            if gap_holds(i) {
+                lower.get_or_insert(match i {
+                    0 => Endpoint::Infinite,
+                    _ => Endpoint::Open(roots[i - 1]),
+                });
+            }
+            if relation.is_strict() {
+                if let Some(lower) = lower.take() {
+                    intervals.push(Interval {
+                        lower,
+                        upper: Endpoint::Open(*root),
+                    });
+                }
+            } else {
+                lower.get_or_insert(Endpoint::Closed(*root));
+                if !gap_holds(i + 1) {
+                    intervals.push(Interval {
+                        lower: lower.take().unwrap(),
+                        upper: Endpoint::Closed(*root),
+                    });
+                }
+            }
+        }
+        if gap_holds(roots.len()) {
+            intervals.push(Interval {
+                lower: lower.unwrap_or(match roots.last() {
+                    Some(root) => Endpoint::Open(*root),
+                    None => Endpoint::Infinite,
+                }),
+                upper: Endpoint::Infinite,
+            });
+        }
+        SolutionSet(intervals)
+    }
+}
+
+impl SolutionSet {
+    /// Removes a single point, splitting the interval that contains it.
+    pub fn excluding(self, point: Root) -> Self {
+        let x = point.value.re;
+        let mut intervals = vec![];
+        for interval in self.0 {
+            let (above, at_lower) = match interval.lower {
+                Endpoint::Infinite => (true, false),
+                Endpoint::Open(root) => (x > root.value.re, false),
+                Endpoint::Closed(root) => (x >= root.value.re, x == root.value.re),
+            };
+            let (below, at_upper) = match interval.upper {
+                Endpoint::Infinite => (true, false),
+                Endpoint::Open(root) => (x < root.value.re, false),
+                Endpoint::Closed(root) => (x <= root.value.re, x == root.value.re),
+            };
+            if !(above && below) {
+                intervals.push(interval);
+                continue;
+            }
+            if !at_lower {
+                intervals.push(Interval {
+                    lower: interval.lower,
+                    upper: Endpoint::Open(point),
+                });
+            }
+            if !at_upper {
+                intervals.push(Interval {
+                    lower: Endpoint::Open(point),
+                    upper: interval.upper,
+                });
+            }
+        }
+        SolutionSet(intervals)
+    }
+}
+
+fn write_value(f: &mut Formatter<'_>, root: &Root) -> std::fmt::Result {
+    match (root.rational, f.precision()) {
+        (Some(rational), _) => write!(f, "{rational}"),
+        (None, Some(precision)) => write!(f, "{:.*}", precision, root.value.re),
+        (None, None) => write!(f, "{}", root.value.re),
+    }
+}
+
+impl Display for Interval {
+    /// Writes e.g. `(-∞, 1)`, `[1/3, 2.414214)` or `{2}` for a single point.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let (Endpoint::Closed(lower), Endpoint::Closed(upper)) = (self.lower, self.upper) {
+            if lower == upper {
+                write!(f, "{{")?;
+                write_value(f, &lower)?;
+                return write!(f, "}}");
+            }
+        }
+        match self.lower {
+            Endpoint::Infinite => write!(f, "(-∞")?,
+            Endpoint::Open(root) => {
+                write!(f, "(")?;
+                write_value(f, &root)?
+            }
+            Endpoint::Closed(root) => {
+                write!(f, "[")?;
+                write_value(f, &root)?
+            }
+        }
+        write!(f, ", ")?;
+        match self.upper {
+            Endpoint::Infinite => write!(f, "+∞)"),
+            Endpoint::Open(root) => {
+                write_value(f, &root)?;
+                write!(f, ")")
+            }
+            Endpoint::Closed(root) => {
+                write_value(f, &root)?;
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl Display for SolutionSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return writeln!(f, "There is no solution");
+        }
+        writeln!(f, "The solution set is:")?;
+        for (i, interval) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ∪ ")?;
+            }
+            match f.precision() {
+                Some(precision) => write!(f, "{:.*}", precision, interval)?,
+                None => write!(f, "{interval}")?,
+            }
+        }
+        writeln!(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{complex::Complex, rational::Rational};
+
+    fn root(value: i128) -> Root {
+        Root {
+            value: Complex::from(value as f64),
+            multiplicity: 1,
+            rational: Some(Rational::from(value)),
+            surd: None,
+            residual: 0.0,
+            polished: Complex::from(value as f64),
+            verified: true,
+        }
+    }
+
+    fn solve(relation: Relation, roots: &[i128], evaluate: impl Fn(f64) -> f64) -> String {
+        let roots = roots.iter().copied().map(root).collect::<Vec<_>>();
+        SolutionSet::new(relation, &roots, evaluate).to_string()
+    }
+
+    #[test]
+    fn test_quadratic() {
+        // (X - 1)(X - 3)
+        let evaluate = |x: f64| (x - 1.0) * (x - 3.0);
+        assert_eq!(
+            "The solution set is:\n(-∞, 1) ∪ (3, +∞)\n",
+            solve(Relation::Greater, &[1, 3], evaluate)
+        );
+        assert_eq!(
+            "The solution set is:\n[1, 3]\n",
+            solve(Relation::LessEqual, &[1, 3], evaluate)
+        );
+    }
+
+    #[test]
+    fn test_double_root() {
+        // (X - 2)^2
+        let evaluate = |x: f64| (x - 2.0) * (x - 2.0);
+        assert_eq!(
+            "There is no solution\n",
+            solve(Relation::Less, &[2], evaluate)
+        );
+        assert_eq!(
+            "The solution set is:\n{2}\n",
+            solve(Relation::LessEqual, &[2], evaluate)
+        );
+        assert_eq!(
+            "The solution set is:\n(-∞, 2) ∪ (2, +∞)\n",
+            solve(Relation::Greater, &[2], evaluate)
+        );
+        assert_eq!(
+            "The solution set is:\n(-∞, +∞)\n",
+            solve(Relation::GreaterEqual, &[2], evaluate)
+        );
+    }
+
+    #[test]
+    fn test_constant() {
+        assert_eq!(
+            "The solution set is:\n(-∞, +∞)\n",
+            solve(Relation::Less, &[], |_| -1.0)
+        );
+        assert_eq!(
+            "There is no solution\n",
+            solve(Relation::Greater, &[], |_| 0.0)
+        );
+        assert_eq!(
+            "The solution set is:\n(-∞, +∞)\n",
+            solve(Relation::GreaterEqual, &[], |_| 0.0)
+        );
+    }
+
+    #[test]
+    fn test_excluding() {
+        let roots = [-1, 1].map(root);
+        // X^2 - 1 >= 0 without 1, then X^2 - 1 <= 0 without 0.
+        let set = SolutionSet::new(Relation::GreaterEqual, &roots, |x| x * x - 1.0);
+        assert_eq!(
+            "The solution set is:\n(-∞, -1] ∪ (1, +∞)\n",
+            set.excluding(root(1)).to_string()
+        );
+        let set = SolutionSet::new(Relation::LessEqual, &roots, |x| x * x - 1.0);
+        assert_eq!(
+            "The solution set is:\n[-1, 0) ∪ (0, 1]\n",
+            set.excluding(root(0)).to_string()
+        );
+    }
+}
@@ -0,0 +1,62 @@
+mod cubic;
+mod inequality;
+mod numeric;
+mod quartic;
+mod solution;
+mod symbolic;
+mod system;
+
+use crate::types::complex::Complex;
+
+pub use cubic::cubic;
+pub use inequality::{Endpoint, Interval, Relation, SolutionSet};
+pub use numeric::{evaluate, evaluate_terms, Numeric};
+pub use quartic::quartic;
+pub use solution::{Nature, Notated, Notation, Root, Solution};
+pub use symbolic::SymbolicSolution;
+pub use system::{LinearSystem, Parametrized, SystemSolution};
+
+/// How `Polynomial::solve` finds the roots.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum Strategy {
+    /// Closed-form formulas up to degree 4, falling back to the numeric solver beyond.
+    #[default]
+    ClosedForm,
+    Numeric(Numeric),
+}
+
+/// Relative residual a root may keep after snapping it onto the real axis.
+const TOLERANCE: f64 = 1e-9;
+
+/// Orders roots by real and then imaginary part. A root is snapped onto the real axis when its
+/// real part alone satisfies the polynomial, which absorbs both round-off from complex
+/// arithmetic and the spurious conjugate pairs that repeated real roots split into.
+fn settle(coefficients: &[f64], roots: impl IntoIterator<Item = Complex>) -> Vec<Complex> {
+    let mut roots: Vec<Complex> = roots
+        .into_iter()
+        .map(|root| {
+            let real = Complex::from(root.re);
+            if !root.is_real() && numeric::relative_residual(coefficients, real) <= TOLERANCE {
+                real
+            } else {
+                root
+            }
+        })
+        .collect();
+    // Real parts are compared to 12 significant digits, so that the round-off between the two
+    // roots of a conjugate pair does not decide their order.
+    let rounded = |x: f64| match x {
+        0.0 => x,
+        _ => {
+            let scale = 10_f64.powi(12 - x.abs().log10().ceil() as i32);
+            (x * scale).round() / scale
+        }
+    };
+    roots.sort_by(|a, b| {
+        rounded(a.re)
+            .partial_cmp(&rounded(b.re))
+            .unwrap()
+            .then(a.im.partial_cmp(&b.im).unwrap())
+    });
+    roots
+}
@@ -0,0 +1,248 @@
+use crate::types::complex::Complex;
+use anyhow::{anyhow, Result};
+use std::f64::consts::PI;
+
+/// Horner evaluation of the polynomial whose `coefficients` are indexed by exponent.
+pub fn evaluate(coefficients: &[f64], z: Complex) -> Complex {
+    evaluate_terms(coefficients.iter().copied().enumerate().rev(), z)
+}
+
+/// Horner evaluation from the coefficients paired with their exponents in decreasing order.
+/// Each step multiplies by `z` raised to the gap down to the next exponent, so that the
+/// missing terms of a sparse polynomial such as `X^1000000 - 1` cost nothing.
+pub fn evaluate_terms(terms: impl IntoIterator<Item = (usize, f64)>, z: Complex) -> Complex {
+    let (value, lowest) = terms.into_iter().fold(
+        (Complex::default(), None),
+        |(value, previous), (exponent, coefficient)| {
+            let value = match previous {
+                Some(previous) => value * z.powi(previous - exponent),
+                None => value,
+            };
+            (value + Complex::from(coefficient), Some(exponent))
+        },
+    );
+    match lowest {
+        Some(lowest) if lowest > 0 => value * z.powi(lowest),
+        _ => value,
+    }
+}
+
+/// Residual |p(z)| relative to the magnitude of the summed terms, so that it does not depend
+/// on the scale of the coefficients or of the root.
+pub fn relative_residual(coefficients: &[f64], z: Complex) -> f64 {
+    let scale = coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |acc, coefficient| acc * z.norm() + coefficient.abs());
+    if scale == 0.0 {
+        0.0
+    } else {
+        evaluate(coefficients, z).norm() / scale
+    }
+}
+
+/// Horner evaluation of both the polynomial and its derivative.
+pub fn evaluate_with_derivative(coefficients: &[f64], z: Complex) -> (Complex, Complex) {
+    coefficients.iter().rev().fold(
+        (Complex::default(), Complex::default()),
+        |(value, derivative), coefficient| {
+            (
+                value * z + Complex::from(*coefficient),
+                derivative * z + value,
+            )
+        },
+    )
+}
+
+/// Newton correction `p(z) / p'(z)`. Outside the unit circle the powers of `z` may overflow,
+/// so it is computed from the reversed polynomial `q(w) = w^n p(1/w)` at `w = 1/z` instead,
+/// using `p(z) / p'(z) = z q(w) / (n q(w) - w q'(w))`.
+fn newton_correction(coefficients: &[f64], reversed: &[f64], z: Complex) -> Complex {
+    if z.norm() <= 1.0 {
+        let (value, derivative) = evaluate_with_derivative(coefficients, z);
+        return value / derivative;
+    }
+    let w = Complex::from(1.0) / z;
+    let (value, derivative) = evaluate_with_derivative(reversed, w);
+    let degree = Complex::from((coefficients.len() - 1) as f64);
+    z * value / (degree * value - w * derivative)
+}
+
+/// Relative residual within the rounding error of evaluating a polynomial, below which no step
+/// can improve a root any more.
+const ROUNDING: f64 = 64.0 * f64::EPSILON;
+
+/// Simultaneous root finder based on the Aberth–Ehrlich iteration, which works for polynomials
+/// of any degree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Numeric {
+    /// Largest relative correction that still counts as converged.
+    pub tolerance: f64,
+    pub max_iterations: usize,
+}
+
+impl Default for Numeric {
+    fn default() -> Self {
+        Numeric {
+            tolerance: 1e-14,
+            max_iterations: 1000,
+        }
+    }
+}
+
+impl Numeric {
+    /// Also stops once the corrections no longer decrease while every residual is down to
+    /// rounding, which is as close as clustered roots such as 1 and 1.00001 get. Fails when
+    /// some correction is still above the tolerance, or is not even finite, after the last
+    /// iteration.
+    pub fn roots(&self, coefficients: &[f64]) -> Result<Vec<Complex>> {
+        let degree = coefficients.len() - 1;
+        let leading = coefficients[degree];
+        let reversed = coefficients.iter().rev().copied().collect::<Vec<_>>();
+
+        // Start from points spread over a circle of radius max |a(n-k) / a(n)|^(1/k), the size
+        // of the largest roots, slightly rotated so that no guess lies on a symmetry axis of a
+        // real polynomial. Unlike Cauchy's bound 1 + max |a(k) / a(n)|, it does not exceed 1
+        // when the roots are of modulus 1, as those of X^1000 - 1.
+        let radius = coefficients[..degree]
+            .iter()
+            .rev()
+            .enumerate()
+            .fold(0.0_f64, |max, (k, c)| {
+                max.max((c / leading).abs().powf(1.0 / (k + 1) as f64))
+            });
+        let radius = if radius > 0.0 { radius } else { 1.0 };
+        let mut roots: Vec<Complex> = (0..degree)
+            .map(|k| {
+                let angle = 2.0 * PI * k as f64 / degree as f64 + 0.4;
+                Complex::new(radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+
+        let mut largest_step = f64::INFINITY;
+        let mut settled = false;
+        for _ in 0..self.max_iterations {
+            let previous_step = largest_step;
+            largest_step = 0.0;
+            for i in 0..degree {
+                let newton = newton_correction(coefficients, &reversed, roots[i]);
+                if newton == Complex::default() {
+                    continue;
+                }
+                let repulsion = (0..degree)
+                    .filter(|&j| j != i)
+                    .fold(Complex::default(), |acc, j| {
+                        acc + Complex::from(1.0) / (roots[i] - roots[j])
+                    });
+                let step = newton / (Complex::from(1.0) - newton * repulsion);
+                if step.re.is_finite() && step.im.is_finite() {
+                    roots[i] = roots[i] - step;
+                    largest_step = largest_step.max(step.norm() / roots[i].norm().max(1.0));
+                } else {
+                    largest_step = f64::INFINITY;
+                }
+            }
+            if largest_step < self.tolerance {
+                break;
+            }
+            settled = largest_step >= previous_step
+                && roots
+                    .iter()
+                    .all(|root| relative_residual(coefficients, *root) <= ROUNDING);
+            if settled {
+                break;
+            }
+        }
+        if largest_step >= self.tolerance && !settled {
+            return Err(anyhow!(
+                "{}{}",
+                "NUMERIC_CONVERGENCE_ERR",
+                self.max_iterations
+            ));
+        }
+        Ok(super::settle(coefficients, roots))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roots() {
+        let expected = [
+            Complex::from(-1.0),
+            Complex::new(0.0, -1.0),
+            Complex::new(0.0, 1.0),
+            Complex::from(1.0),
+        ];
+        let roots = Numeric::default()
+            .roots(&[-1.0, 0.0, 0.0, 0.0, 1.0])
+            .unwrap();
+        for (expected, root) in expected.iter().zip(roots) {
+            assert!((*expected - root).norm() < 1e-9, "{} != {}", expected, root);
+        }
+    }
+
+    #[test]
+    fn test_close_roots() {
+        // (x - 1)(x - 1.00001)(x + 2.00001): the corrections of the two close roots stall at
+        // the rounding error, just above the tolerance.
+        let roots = Numeric::default()
+            .roots(&[2.0000300001, -3.0000300001, 0.0, 1.0])
+            .unwrap();
+        for expected in [-2.00001, 1.0, 1.00001] {
+            assert!(roots
+                .iter()
+                .any(|root| (*root - Complex::from(expected)).norm() < 1e-7));
+        }
+    }
+
+    #[test]
+    fn test_high_degree() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)(x - 5)
+        let coefficients = [-120.0, 274.0, -225.0, 85.0, -15.0, 1.0];
+        let roots = Numeric::default().roots(&coefficients).unwrap();
+        assert_eq!(5, roots.len());
+        for (expected, root) in (1..=5).zip(roots) {
+            assert!((Complex::from(expected as f64) - root).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_iteration_cap() {
+        let numeric = Numeric {
+            max_iterations: 0,
+            ..Default::default()
+        };
+        assert!(numeric
+            .roots(&[-120.0, 274.0, -225.0, 85.0, -15.0, 1.0])
+            .is_err());
+        let numeric = Numeric {
+            max_iterations: 2,
+            ..Default::default()
+        };
+        assert!(numeric
+            .roots(&[-120.0, 274.0, -225.0, 85.0, -15.0, 1.0])
+            .is_err());
+    }
+
+    #[test]
+    fn test_large_degree() {
+        // X^1000 - 1, whose values overflow far outside the unit circle.
+        let mut coefficients = vec![0.0; 1001];
+        (coefficients[0], coefficients[1000]) = (-1.0, 1.0);
+        let roots = Numeric::default().roots(&coefficients).unwrap();
+        assert_eq!(1000, roots.len());
+        for root in roots {
+            assert!((root.norm() - 1.0).abs() < 1e-9);
+            assert!(relative_residual(&coefficients, root) < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_relative_residual() {
+        assert_eq!(0.0, relative_residual(&[-2.0, 1.0], Complex::from(2.0)));
+        assert!(relative_residual(&[-2.0, 1.0], Complex::from(2.1)) > 1e-3);
+    }
+}
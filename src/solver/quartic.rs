@@ -0,0 +1,110 @@
+use super::{cubic, numeric, Numeric};
+use crate::types::complex::Complex;
+
+/// Relative residual above which the closed form is considered to have lost too much precision.
+const TOLERANCE: f64 = 1e-12;
+
+/// Solves `a * x^4 + b * x^3 + c * x^2 + d * x + e = 0` with Ferrari's method, falling back to
+/// a numeric solver when cancellation makes the closed form unreliable, unless that one does
+/// not converge either.
+pub fn quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<Complex> {
+    let roots = ferrari(b / a, c / a, d / a, e / a);
+    let coefficients = [e, d, c, b, a];
+
+    if roots
+        .iter()
+        .all(|root| numeric::relative_residual(&coefficients, *root) <= TOLERANCE)
+    {
+        super::settle(&coefficients, roots)
+    } else {
+        Numeric::default()
+            .roots(&coefficients)
+            .unwrap_or_else(|_| super::settle(&coefficients, roots))
+    }
+}
+
+/// Ferrari's method for the monic quartic `x^4 + b * x^3 + c * x^2 + d * x + e`.
+fn ferrari(b: f64, c: f64, d: f64, e: f64) -> Vec<Complex> {
+    // Substituting x = y - b / 4 yields the depressed quartic y^4 + p * y^2 + q * y + r = 0.
+    let shift = Complex::from(-b / 4.0);
+    let p = c - 3.0 * b * b / 8.0;
+    let q = (b.powi(3) - 4.0 * b * c + 8.0 * d) / 8.0;
+    let r = (-3.0 * b.powi(4) + 256.0 * e - 64.0 * b * d + 16.0 * b * b * c) / 256.0;
+
+    let roots = if q == 0.0 {
+        // Biquadratic: solve for z = y^2 and take both square roots of each solution.
+        let discriminant = Complex::from(p * p - 4.0 * r).sqrt();
+        [
+            Complex::from(-p) - discriminant,
+            Complex::from(-p) + discriminant,
+        ]
+        .iter()
+        .flat_map(|z| {
+            let y = (*z / Complex::from(2.0)).sqrt();
+            vec![-y, y]
+        })
+        .collect::<Vec<_>>()
+    } else {
+        // Any positive root m of the resolvent cubic splits the quartic into two quadratics.
+        let m = cubic(8.0, 8.0 * p, 2.0 * p * p - 8.0 * r, -q * q)
+            .into_iter()
+            .filter(|root| root.is_real())
+            .map(|root| root.re)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let s = (2.0 * m).sqrt();
+        [1.0, -1.0]
+            .iter()
+            .flat_map(|sign| {
+                let root =
+                    Complex::from(-(2.0 * p + 2.0 * m + sign * 2.0_f64.sqrt() * q / m.sqrt()))
+                        .sqrt();
+                let base = Complex::from(sign * s);
+                vec![
+                    (base - root) / Complex::from(2.0),
+                    (base + root) / Complex::from(2.0),
+                ]
+            })
+            .collect()
+    };
+    roots.into_iter().map(|root| root + shift).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roots(expected: &[Complex], roots: Vec<Complex>) {
+        assert_eq!(expected.len(), roots.len());
+        for (expected, root) in expected.iter().zip(roots) {
+            assert!((*expected - root).norm() < 1e-9, "{} != {}", expected, root);
+        }
+    }
+
+    #[test]
+    fn test_four_real_roots() {
+        let expected = [-2.0, 1.0, 3.0, 4.0].map(Complex::from);
+        assert_roots(&expected, quartic(1.0, -6.0, 3.0, 26.0, -24.0));
+    }
+
+    #[test]
+    fn test_complex_roots() {
+        let expected = [
+            Complex::new(-1.0, -1.0),
+            Complex::new(-1.0, 1.0),
+            Complex::new(1.0, -2.0),
+            Complex::new(1.0, 2.0),
+        ];
+        assert_roots(&expected, quartic(1.0, 0.0, 3.0, 6.0, 10.0));
+    }
+
+    #[test]
+    fn test_biquadratic() {
+        let expected = [
+            Complex::from(-1.0),
+            Complex::new(0.0, -2.0),
+            Complex::new(0.0, 2.0),
+            Complex::from(1.0),
+        ];
+        assert_roots(&expected, quartic(1.0, 0.0, 3.0, 0.0, -4.0));
+    }
+}
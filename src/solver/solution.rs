@@ -0,0 +1,405 @@
+use super::{numeric, TOLERANCE};
+use crate::types::{complex::Complex, rational::Rational, surd::Surd};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+/// Largest number of Newton steps spent polishing a root.
+const NEWTON_STEPS: usize = 8;
+
+/// What kind of roots an equation has.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Nature {
+    /// The equation reduces to `0 = 0`.
+    AllReals,
+    /// The equation reduces to a non-zero constant equal to zero.
+    NoSolution,
+    DistinctReal,
+    /// Only real roots, at least one of which is repeated (a double root for quadratics).
+    Repeated,
+    /// At least one conjugate pair of non-real roots.
+    ComplexPairs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Root {
+    pub value: Complex,
+    pub multiplicity: usize,
+    /// Exact value when the root is rational.
+    pub rational: Option<Rational>,
+    /// Exact value when the root is a quadratic irrational.
+    pub surd: Option<Surd>,
+    /// `|p(value)|`, which vanishes for an exact root.
+    pub residual: f64,
+    /// The value after Newton's method refined it on the whole polynomial.
+    pub polished: Complex,
+    /// Whether the residual, relative to the magnitude of the terms of `p(value)`, is within
+    /// tolerance.
+    pub verified: bool,
+}
+
+impl From<Rational> for Root {
+    /// Exact simple root.
+    fn from(rational: Rational) -> Self {
+        let value = Complex::from(f64::from(rational));
+        Root {
+            value,
+            multiplicity: 1,
+            rational: Some(rational),
+            surd: None,
+            residual: 0.0,
+            polished: value,
+            verified: true,
+        }
+    }
+}
+
+/// Everything a front end needs to report the outcome of `Polynomial::solve`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub degree: usize,
+    pub discriminant: Option<f64>,
+    pub nature: Nature,
+    pub roots: Vec<Root>,
+}
+
+impl Solution {
+    /// Solution of the degree-0 equation `constant = 0`.
+    pub fn constant(constant: f64) -> Self {
+        Solution {
+            degree: 0,
+            discriminant: None,
+            nature: if constant == 0.0 {
+                Nature::AllReals
+            } else {
+                Nature::NoSolution
+            },
+            roots: vec![],
+        }
+    }
+
+    /// Groups sorted roots, in which a repeated root is listed once per multiplicity.
+    pub fn from_roots(degree: usize, discriminant: Option<f64>, roots: Vec<Complex>) -> Self {
+        let roots = roots
+            .into_iter()
+            .dedup_with_count()
+            .map(|(multiplicity, value)| Root {
+                value,
+                multiplicity,
+                rational: None,
+                surd: None,
+                residual: 0.0,
+                polished: value,
+                verified: true,
+            })
+            .collect_vec();
+        let nature = if roots.iter().any(|root| !root.value.is_real()) {
+            Nature::ComplexPairs
+        } else if roots.iter().any(|root| root.multiplicity > 1) {
+            Nature::Repeated
+        } else {
+            Nature::DistinctReal
+        };
+        Solution {
+            degree,
+            discriminant,
+            nature,
+            roots,
+        }
+    }
+
+    /// Attaches the exact value to every root that equals one of `rational_roots`.
+    pub fn with_rational_roots(mut self, rational_roots: &[Rational]) -> Self {
+        for root in self.roots.iter_mut() {
+            root.rational = rational_roots
+                .iter()
+                .find(|rational| Complex::from(f64::from(**rational)) == root.value)
+                .copied();
+        }
+        self
+    }
+
+    /// Attaches to every root its residual in the polynomial with the given coefficients, and
+    /// the value a few Newton steps polish it to. The steps are scaled by the multiplicity,
+    /// which keeps the convergence quadratic on repeated roots.
+    pub fn with_residuals(mut self, coefficients: &[f64]) -> Self {
+        for root in self.roots.iter_mut() {
+            root.residual = numeric::evaluate(coefficients, root.value).norm();
+            root.verified = numeric::relative_residual(coefficients, root.value) <= TOLERANCE;
+            let mut polished = root.value;
+            let mut residual = root.residual;
+            for _ in 0..NEWTON_STEPS {
+                let (value, derivative) = numeric::evaluate_with_derivative(coefficients, polished);
+                if residual == 0.0 || derivative.norm() == 0.0 {
+                    break;
+                }
+                let next = polished - Complex::from(root.multiplicity as f64) * value / derivative;
+                let next_residual = numeric::evaluate(coefficients, next).norm();
+                if next_residual.is_nan() || next_residual >= residual {
+                    break;
+                }
+                (polished, residual) = (next, next_residual);
+            }
+            root.polished = polished;
+        }
+        self
+    }
+
+    /// Drops the root equal to `point`, which is not a solution of the original equation,
+    /// e.g. because it makes a denominator zero.
+    pub fn excluding(mut self, point: Root) -> Self {
+        self.roots
+            .retain(|root| match (root.rational, point.rational) {
+                (Some(a), Some(b)) => a != b,
+                _ => (root.value - point.value).norm() > 1e-9 * point.value.norm().max(1.0),
+            });
+        if self.roots.is_empty() && self.nature != Nature::AllReals {
+            self.nature = Nature::NoSolution;
+        }
+        self
+    }
+
+    /// Attaches the exact value to every root that matches one of `surds` numerically.
+    pub fn with_surds(mut self, surds: &[Surd]) -> Self {
+        for root in self.roots.iter_mut() {
+            root.surd = surds
+                .iter()
+                .find(|surd| {
+                    (surd.to_complex() - root.value).norm() <= 1e-9 * root.value.norm().max(1.0)
+                })
+                .copied();
+        }
+        self
+    }
+}
+
+/// How the values of roots are written out.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Notation {
+    #[default]
+    Decimal,
+    /// Rational roots as irreducible fractions, any other root in decimal.
+    Fraction,
+    /// Exact forms where known, followed by the decimal value of irrational roots.
+    Exact,
+}
+
+/// A `Solution` written out in a given `Notation`, repeated roots naming the unknown as in
+/// `X = 2 (multiplicity 3)`.
+pub struct Notated<'a> {
+    solution: &'a Solution,
+    notation: Notation,
+    unknown: &'a str,
+}
+
+impl Solution {
+    pub fn notated(&self, notation: Notation) -> Notated<'_> {
+        Notated {
+            solution: self,
+            notation,
+            unknown: "X",
+        }
+    }
+}
+
+impl<'a> Notated<'a> {
+    pub fn with_unknown(mut self, unknown: &'a str) -> Self {
+        self.unknown = unknown;
+        self
+    }
+}
+
+fn write_decimal(f: &mut Formatter<'_>, value: Complex) -> std::fmt::Result {
+    match f.precision() {
+        Some(precision) => write!(f, "{:.*}", precision, value),
+        None => write!(f, "{}", value),
+    }
+}
+
+fn write_multiplicity(f: &mut Formatter<'_>, multiplicity: usize) -> std::fmt::Result {
+    match multiplicity {
+        1 => Ok(()),
+        multiplicity => write!(f, " (multiplicity {multiplicity})"),
+    }
+}
+
+impl Display for Root {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_decimal(f, self.value)?;
+        write_multiplicity(f, self.multiplicity)
+    }
+}
+
+impl Display for Notated<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use Nature::*;
+
+        let solution = self.solution;
+        // Headers naming the number of roots only hold while all of them are listed.
+        let complete =
+            solution.roots.iter().map(|root| root.multiplicity).sum::<usize>() == solution.degree;
+        let header = match (solution.nature, solution.degree) {
+            (AllReals, _) => return writeln!(f, "Each real number is a solution"),
+            (NoSolution, _) => return writeln!(f, "There is no solution"),
+            _ if !complete && solution.roots.len() == 1 => "The solution is:",
+            _ if !complete => "The solutions are:",
+            (_, 1) => "The solution is:",
+            (DistinctReal, 2) => "Discriminant is strictly positive, the two solutions are:",
+            (ComplexPairs, 2) => "Discriminant is strictly negative, the two complex solutions are:",
+            (Repeated, 2) => "Discriminant is zero, the solution is:",
+            (DistinctReal, 3) => "Discriminant is strictly positive, the three real solutions are:",
+            (ComplexPairs, 3) => {
+                "Discriminant is strictly negative, the real and the two complex solutions are:"
+            }
+            (Repeated, 3) if solution.roots.len() == 1 => "Discriminant is zero, the solution is:",
+            (Repeated, 3) => "Discriminant is zero, the solutions are:",
+            (DistinctReal, 4) => "The four real solutions are:",
+            (ComplexPairs, 4) if solution.roots.len() == 4 => "The four solutions are:",
+            _ => "The solutions are:",
+        };
+        writeln!(f, "{header}")?;
+        for root in &solution.roots {
+            let repeated = root.multiplicity > 1;
+            if repeated {
+                write!(f, "{} = ", self.unknown)?;
+            }
+            match (self.notation, root.rational, root.surd) {
+                (Notation::Fraction | Notation::Exact, Some(rational), _) => {
+                    write!(f, "{rational:#}")?
+                }
+                (_, Some(rational), _) if repeated && rational.is_integer() => {
+                    write!(f, "{rational}")?
+                }
+                (Notation::Exact, None, Some(surd)) => {
+                    write!(f, "{surd} ≈ ")?;
+                    write_decimal(f, root.value)?
+                }
+                _ => write_decimal(f, root.value)?,
+            }
+            write_multiplicity(f, root.multiplicity)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.notated(Notation::Decimal).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nature() {
+        assert_eq!(Nature::AllReals, Solution::constant(0.0).nature);
+        assert_eq!(Nature::NoSolution, Solution::constant(4.0).nature);
+
+        let roots = [1.0, 2.0].map(Complex::from).to_vec();
+        assert_eq!(Nature::DistinctReal, Solution::from_roots(2, Some(1.0), roots).nature);
+
+        let roots = [Complex::new(1.0, -1.0), Complex::new(1.0, 1.0)].to_vec();
+        assert_eq!(Nature::ComplexPairs, Solution::from_roots(2, Some(-4.0), roots).nature);
+    }
+
+    #[test]
+    fn test_multiplicities() {
+        let roots = [1.0, 1.0, 2.0].map(Complex::from).to_vec();
+        let solution = Solution::from_roots(3, Some(0.0), roots);
+        assert_eq!(Nature::Repeated, solution.nature);
+        assert_eq!(
+            vec![
+                Root {
+                    value: Complex::from(1.0),
+                    multiplicity: 2,
+                    rational: None,
+                    surd: None,
+                    residual: 0.0,
+                    polished: Complex::from(1.0),
+                    verified: true
+                },
+                Root {
+                    value: Complex::from(2.0),
+                    multiplicity: 1,
+                    rational: None,
+                    surd: None,
+                    residual: 0.0,
+                    polished: Complex::from(2.0),
+                    verified: true
+                }
+            ],
+            solution.roots
+        );
+        assert_eq!(
+            "Discriminant is zero, the solutions are:\nX = 1.00 (multiplicity 2)\n2.00\n",
+            format!("{:.2}", solution)
+        );
+        let solution = solution.with_rational_roots(&[Rational::ONE]);
+        assert_eq!(
+            "Discriminant is zero, the solutions are:\nY = 1 (multiplicity 2)\n2.00\n",
+            format!("{:.2}", solution.notated(Notation::Decimal).with_unknown("Y"))
+        );
+    }
+
+    #[test]
+    fn test_rational_roots() {
+        let roots = [-0.25, 2.0_f64.sqrt()].map(Complex::from).to_vec();
+        let solution =
+            Solution::from_roots(2, Some(1.0), roots).with_rational_roots(&[Rational::new(-1, 4)]);
+        assert_eq!(
+            "Discriminant is strictly positive, the two solutions are:\n-1/4\n1.414214\n",
+            format!("{:.6}", solution.notated(Notation::Fraction))
+        );
+    }
+
+    #[test]
+    fn test_surds() {
+        let roots = [-(2.0_f64.sqrt()), 2.0_f64.sqrt()].map(Complex::from).to_vec();
+        let surds = Surd::quadratic_roots(Rational::ONE, Rational::ZERO, Rational::from(-2));
+        let solution = Solution::from_roots(2, Some(8.0), roots).with_surds(&surds.unwrap());
+        assert_eq!(
+            "Discriminant is strictly positive, the two solutions are:\n-√2 ≈ -1.414\n√2 ≈ 1.414\n",
+            format!("{:.3}", solution.notated(Notation::Exact))
+        );
+        assert_eq!(
+            "Discriminant is strictly positive, the two solutions are:\n-1.414\n1.414\n",
+            format!("{:.3}", solution.notated(Notation::Fraction))
+        );
+    }
+
+    #[test]
+    fn test_residuals() {
+        // (X - 1)^2 = X^2 - 2X + 1, with a root off by 1e-3.
+        let roots = vec![Complex::from(1.001); 2];
+        let solution = Solution::from_roots(2, Some(0.0), roots).with_residuals(&[1.0, -2.0, 1.0]);
+        let root = solution.roots[0];
+        assert!((root.residual - 1e-6).abs() < 1e-12);
+        assert!(!root.verified);
+        assert!((root.polished.re - 1.0).abs() < 1e-9);
+
+        let solution = Solution::from_roots(1, None, vec![Complex::from(0.5)])
+            .with_residuals(&[-1.0, 2.0]);
+        assert_eq!(0.0, solution.roots[0].residual);
+        assert!(solution.roots[0].verified);
+    }
+
+    #[test]
+    fn test_excluding() {
+        let roots = [0.0, 2.0].map(Complex::from).to_vec();
+        let solution = Solution::from_roots(2, Some(4.0), roots)
+            .with_rational_roots(&[Rational::ZERO, Rational::from(2)])
+            .excluding(Root::from(Rational::ZERO));
+        assert_eq!("The solution is:\n2.00\n", format!("{:.2}", solution));
+
+        let solution = Solution::from_roots(1, None, vec![Complex::from(0.0)]);
+        assert_eq!(Nature::NoSolution, solution.excluding(Root::from(Rational::ZERO)).nature);
+
+        // An irrational pole is matched numerically.
+        let root = |value: f64| Solution::from_roots(1, None, vec![Complex::from(value)]).roots[0];
+        let solution = Solution::from_roots(1, None, vec![Complex::from(2f64.sqrt())]);
+        let pole = root(2f64.sqrt() * (1.0 + 1e-12));
+        assert_eq!(Nature::NoSolution, solution.excluding(pole).nature);
+    }
+}
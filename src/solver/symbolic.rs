@@ -0,0 +1,190 @@
+use crate::types::expression::Expression;
+use crate::types::rational::{Rational, OVERFLOW_ERR};
+use anyhow::{anyhow, Context, Result};
+use std::fmt::{Display, Formatter};
+
+/// Roots of a polynomial whose coefficients depend on symbolic parameters, written as
+/// formulas in those parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolicSolution {
+    /// The unknown does not appear, so any value solves the equation exactly when the
+    /// remaining expression vanishes.
+    Independent {
+        unknown: String,
+        constant: Expression,
+    },
+    Roots {
+        unknown: String,
+        roots: Vec<String>,
+        /// Leading coefficient, which the formulas divide by.
+        leading: Expression,
+    },
+}
+
+/// `numerator / denominator`, divided out when the denominator is a constant.
+fn quotient(numerator: &Expression, denominator: &Expression) -> Result<String> {
+    match denominator.as_constant() {
+        Some(constant) => Ok(numerator
+            .checked_scale(constant.checked_recip().context("SYMBOLIC_DIVISION_ERR")?)
+            .context(OVERFLOW_ERR)?
+            .to_string()),
+        None if numerator.is_zero() => Ok("0".to_owned()),
+        None => Ok(format!("{}/{}", numerator.grouped(), denominator.grouped())),
+    }
+}
+
+impl SymbolicSolution {
+    /// Applies the linear or the quadratic formula to the coefficients of `unknown`, indexed
+    /// by exponent.
+    pub fn new(unknown: &str, coefficients: &[Expression]) -> Result<Self> {
+        let unknown = unknown.to_owned();
+        let roots = match coefficients {
+            [] => {
+                return Ok(SymbolicSolution::Independent {
+                    unknown,
+                    constant: Expression::default(),
+                })
+            }
+            [constant] => {
+                return Ok(SymbolicSolution::Independent {
+                    unknown,
+                    constant: constant.clone(),
+                })
+            }
+            [b, a] => vec![quotient(&b.checked_neg().context(OVERFLOW_ERR)?, a)?],
+            // Without a constant, X = 0 is a root, the only one, and a double one, when b = 0.
+            [c, b, _] if c.is_zero() && b.is_zero() => vec!["0".to_owned()],
+            [c, b, a] if c.is_zero() => {
+                vec![
+                    "0".to_owned(),
+                    quotient(&b.checked_neg().context(OVERFLOW_ERR)?, a)?,
+                ]
+            }
+            [c, b, a] if b.is_zero() => {
+                let square = quotient(&c.checked_neg().context(OVERFLOW_ERR)?, a)?;
+                vec![format!("±√({square})")]
+            }
+            [c, b, a] => {
+                let discriminant = b
+                    .checked_mul(b)
+                    .zip(a.checked_mul(c))
+                    .and_then(|(b2, ac)| b2.checked_add(&ac.checked_scale(Rational::from(-4))?))
+                    .context(OVERFLOW_ERR)?;
+                let numerator = format!(
+                    "{} ± √({discriminant})",
+                    b.checked_neg().context(OVERFLOW_ERR)?.grouped()
+                );
+                let denominator = a.checked_scale(Rational::from(2)).context(OVERFLOW_ERR)?;
+                vec![match denominator.as_constant() {
+                    Some(constant) if constant == Rational::ONE => numerator,
+                    _ => format!("({numerator})/{}", denominator.grouped()),
+                }]
+            }
+            _ => {
+                return Err(anyhow!(
+                    "{}{}",
+                    "SYMBOLIC_DEGREE_ERR",
+                    coefficients.len() - 1
+                ))
+            }
+        };
+        Ok(SymbolicSolution::Roots {
+            unknown,
+            roots,
+            leading: coefficients[coefficients.len() - 1].clone(),
+        })
+    }
+}
+
+impl Display for SymbolicSolution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolicSolution::Independent { unknown, constant } => match constant.as_constant() {
+                Some(constant) if constant.is_zero() => {
+                    writeln!(f, "Each real number is a solution")
+                }
+                Some(_) => writeln!(f, "There is no solution"),
+                None => writeln!(
+                    f,
+                    "Each real number is a solution when {constant} = 0, otherwise there is no \
+                     solution for {unknown}"
+                ),
+            },
+            SymbolicSolution::Roots {
+                unknown,
+                roots,
+                leading,
+            } => {
+                match (roots.len(), leading.as_constant()) {
+                    (1, Some(_)) => writeln!(f, "The solution is:")?,
+                    (_, Some(_)) => writeln!(f, "The solutions are:")?,
+                    (1, None) => writeln!(f, "Provided that {leading} ≠ 0, the solution is:")?,
+                    (_, None) => writeln!(f, "Provided that {leading} ≠ 0, the solutions are:")?,
+                }
+                for root in roots {
+                    writeln!(f, "{unknown} = {root}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameter(name: &str) -> Expression {
+        Expression::term(vec![(name.to_owned(), 1)], Rational::ONE)
+    }
+
+    fn constant(value: i128) -> Expression {
+        Expression::constant(Rational::from(value))
+    }
+
+    #[test]
+    fn test_linear() {
+        let solution = SymbolicSolution::new("X", &[parameter("b"), parameter("a")]).unwrap();
+        assert_eq!(
+            "Provided that a ≠ 0, the solution is:\nX = -b/a\n",
+            solution.to_string()
+        );
+        let solution = SymbolicSolution::new("X", &[parameter("b"), constant(2)]).unwrap();
+        assert_eq!("The solution is:\nX = -1/2 * b\n", solution.to_string());
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let solution =
+            SymbolicSolution::new("X", &[parameter("b"), constant(0), parameter("a")]).unwrap();
+        assert_eq!(
+            "Provided that a ≠ 0, the solution is:\nX = ±√(-b/a)\n",
+            solution.to_string()
+        );
+        let solution =
+            SymbolicSolution::new("X", &[parameter("c"), parameter("b"), parameter("a")]).unwrap();
+        assert_eq!(
+            "Provided that a ≠ 0, the solution is:\nX = (-b ± √(b^2 - 4 * a * c))/(2 * a)\n",
+            solution.to_string()
+        );
+        let solution =
+            SymbolicSolution::new("X", &[constant(0), parameter("b"), constant(1)]).unwrap();
+        assert_eq!("The solutions are:\nX = 0\nX = -b\n", solution.to_string());
+        let solution =
+            SymbolicSolution::new("X", &[constant(0), constant(0), parameter("a")]).unwrap();
+        assert_eq!(
+            "Provided that a ≠ 0, the solution is:\nX = 0\n",
+            solution.to_string()
+        );
+    }
+
+    #[test]
+    fn test_independent() {
+        let solution = SymbolicSolution::new("X", &[parameter("a")]).unwrap();
+        assert_eq!(
+            "Each real number is a solution when a = 0, otherwise there is no solution for X\n",
+            solution.to_string()
+        );
+        assert!(SymbolicSolution::new("X", &vec![constant(1); 4]).is_err());
+    }
+}
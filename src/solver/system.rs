@@ -0,0 +1,216 @@
+use crate::types::matrix::Matrix;
+use crate::types::polynomial::{Polynomial, Term};
+use crate::types::rational::{Rational, OVERFLOW_ERR};
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+/// Linear equations `a1 * x1 + ... + an * xn = b`, kept as the augmented matrix `[A | b]`
+/// whose columns follow the unknowns in alphabetical order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearSystem {
+    pub unknowns: Vec<String>,
+    pub matrix: Matrix,
+}
+
+/// Pivot unknown of an underdetermined system, as a constant plus a combination of the free
+/// unknowns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parametrized {
+    pub unknown: String,
+    pub constant: Rational,
+    pub parameters: Vec<(String, Rational)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SystemSolution {
+    Unique(Vec<(String, Rational)>),
+    Infinite {
+        free: Vec<String>,
+        pivots: Vec<Parametrized>,
+    },
+    None,
+}
+
+impl LinearSystem {
+    /// Reads the equations `polynomial = 0`. Every term must be a constant or a single unknown
+    /// to the power 1.
+    pub fn new(polynomials: &[Polynomial]) -> Result<Self> {
+        let is_constant = |term: &Term| term.identifier.is_none() || term.exponent == Some(0.0);
+        let mut unknowns = vec![];
+        for term in polynomials.iter().flat_map(|polynomial| polynomial.iter()) {
+            if is_constant(term) {
+                continue;
+            }
+            if !term.factors.is_empty()
+                || !matches!(term.exponent, None | Some(1.0))
+                || !matches!(term.operator, None | Some('*'))
+            {
+                return Err(anyhow!("{}{}", "NON_LINEAR_TERM_ERR", term));
+            }
+            unknowns.extend(term.identifier.clone());
+        }
+        let unknowns = unknowns.into_iter().sorted().dedup().collect_vec();
+
+        let mut matrix = Matrix::zeros(polynomials.len(), unknowns.len() + 1);
+        for (row, polynomial) in polynomials.iter().enumerate() {
+            for term in polynomial.iter() {
+                // Constants move to the right-hand side.
+                let (column, coefficient) = match &term.identifier {
+                    Some(identifier) if !is_constant(term) => (
+                        unknowns.binary_search(identifier).unwrap(),
+                        term.coefficient(),
+                    ),
+                    _ => (
+                        unknowns.len(),
+                        term.coefficient().checked_neg().context(OVERFLOW_ERR)?,
+                    ),
+                };
+                matrix[(row, column)] = matrix[(row, column)]
+                    .checked_add(coefficient)
+                    .context(OVERFLOW_ERR)?;
+            }
+        }
+        Ok(LinearSystem { unknowns, matrix })
+    }
+
+    /// Gaussian elimination with partial pivoting. A row reduced to `0 = b` with `b` non-zero
+    /// makes the system inconsistent; otherwise each unknown without a pivot is a parameter.
+    pub fn solve(&self) -> Result<SystemSolution> {
+        let mut matrix = self.matrix.clone();
+        let columns = self.unknowns.len();
+        let pivots = matrix.row_reduce(columns).context(OVERFLOW_ERR)?;
+        if (pivots.len()..matrix.rows()).any(|row| !matrix[(row, columns)].is_zero()) {
+            return Ok(SystemSolution::None);
+        }
+        if pivots.len() == columns {
+            return Ok(SystemSolution::Unique(
+                self.unknowns
+                    .iter()
+                    .enumerate()
+                    .map(|(row, unknown)| (unknown.clone(), matrix[(row, columns)]))
+                    .collect(),
+            ));
+        }
+
+        let free = (0..columns)
+            .filter(|column| !pivots.contains(column))
+            .collect_vec();
+        let mut expressions = vec![];
+        for (row, pivot) in pivots.iter().enumerate() {
+            let mut parameters = vec![];
+            for column in &free {
+                let coefficient = matrix[(row, *column)].checked_neg().context(OVERFLOW_ERR)?;
+                if !coefficient.is_zero() {
+                    parameters.push((self.unknowns[*column].clone(), coefficient));
+                }
+            }
+            expressions.push(Parametrized {
+                unknown: self.unknowns[*pivot].clone(),
+                constant: matrix[(row, columns)],
+                parameters,
+            });
+        }
+        Ok(SystemSolution::Infinite {
+            free: free
+                .iter()
+                .map(|column| self.unknowns[*column].clone())
+                .collect(),
+            pivots: expressions,
+        })
+    }
+}
+
+impl Display for SystemSolution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SystemSolution::Unique(values) => {
+                writeln!(f, "The system has a unique solution:")?;
+                for (unknown, value) in values {
+                    writeln!(f, "{unknown} = {value:#}")?;
+                }
+            }
+            SystemSolution::Infinite { free, pivots } => {
+                writeln!(f, "The system has infinitely many solutions:")?;
+                for pivot in pivots {
+                    write!(f, "{} = ", pivot.unknown)?;
+                    let mut first = true;
+                    if !pivot.constant.is_zero() || pivot.parameters.is_empty() {
+                        write!(f, "{:#}", pivot.constant)?;
+                        first = false;
+                    }
+                    for (parameter, coefficient) in &pivot.parameters {
+                        match (first, coefficient.is_negative()) {
+                            (true, true) => write!(f, "-")?,
+                            (true, false) => {}
+                            (false, true) => write!(f, " - ")?,
+                            (false, false) => write!(f, " + ")?,
+                        }
+                        if coefficient.abs() != Rational::ONE {
+                            write!(f, "{:#} * ", coefficient.abs())?;
+                        }
+                        write!(f, "{parameter}")?;
+                        first = false;
+                    }
+                    writeln!(f)?;
+                }
+                for unknown in free {
+                    writeln!(f, "{unknown} is free")?;
+                }
+            }
+            SystemSolution::None => writeln!(f, "The system has no solution")?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+    use std::convert::TryFrom;
+
+    fn system(input: &str) -> LinearSystem {
+        let polynomials = Parser::new()
+            .parse_system(input)
+            .unwrap()
+            .into_iter()
+            .map(|branch| Polynomial::try_from(branch).unwrap())
+            .collect_vec();
+        LinearSystem::new(&polynomials).unwrap()
+    }
+
+    #[test]
+    fn test_matrix() {
+        let system = system("2 * X + 1 = Y; Y = 3");
+        assert_eq!(vec!["X".to_owned(), "Y".to_owned()], system.unknowns);
+        assert_eq!("[ 2 -1 -1 ]\n[ 0  1  3 ]\n", system.matrix.to_string());
+    }
+
+    #[test]
+    fn test_solutions() {
+        assert_eq!(
+            SystemSolution::Unique(vec![
+                ("X".to_owned(), Rational::from(1)),
+                ("Y".to_owned(), Rational::from(3))
+            ]),
+            system("2 * X + 1 = Y; Y = 3").solve().unwrap()
+        );
+        assert_eq!(
+            "The system has infinitely many solutions:\nX = 1 - 2 * Y + Z\nY is free\nZ is free\n",
+            system("X + 2 * Y - Z = 1; 2 * X + 4 * Y - 2 * Z = 2")
+                .solve()
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            SystemSolution::None,
+            system("X = 1; X = 2").solve().unwrap()
+        );
+        assert!(LinearSystem::new(&[Polynomial::try_from(
+            Parser::new().parse("X * X^2 = 1").unwrap().unwrap()
+        )
+        .unwrap()])
+        .is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use crate::solver::{Nature, Solution, Strategy};
+use crate::types::{polynomial::Polynomial, rational::Rational};
+use anyhow::Result;
+use std::fmt::{Display, Formatter};
+
+/// Numbered derivation printed by `--steps`.
+#[derive(Debug, Default)]
+pub struct Steps(Vec<String>);
+
+impl Steps {
+    pub fn record(&mut self, step: String) {
+        self.0.push(step);
+    }
+
+    /// Records how `solution` follows from the reduced `polynomial`: the discriminant, if any,
+    /// and the formula or method that produced the roots. Fails on a non-polynomial term,
+    /// see `Polynomial::coefficients`.
+    pub fn record_solution(
+        &mut self,
+        polynomial: &Polynomial,
+        solution: &Solution,
+        strategy: &Strategy,
+    ) -> Result<()> {
+        let coefficients = polynomial.coefficients()?;
+        let x = polynomial.identifier();
+        match solution.nature {
+            Nature::AllReals => {
+                self.record(format!(
+                    "Every coefficient is zero, so any {x} satisfies 0 = 0"
+                ));
+                return Ok(());
+            }
+            Nature::NoSolution => {
+                self.record(format!(
+                    "The equation reduces to {} = 0, which never holds",
+                    coefficients[0]
+                ));
+                return Ok(());
+            }
+            _ => {}
+        }
+        if let Strategy::Numeric(numeric) = strategy {
+            self.record(format!(
+                "Approximate all roots at once with the Aberth–Ehrlich iteration (tolerance {:e}, at most {} iterations)",
+                numeric.tolerance, numeric.max_iterations
+            ));
+            return Ok(());
+        }
+
+        match *coefficients.as_slice() {
+            [b, a] => self.record(match b.checked_neg().and_then(|b| b.checked_div(a)) {
+                Some(root) => format!("Isolate {x}: {x} = -b / a = -({b}) / {a} = {root:#}"),
+                None => format!("Isolate {x}: {x} = -b / a = -({b}) / {a}"),
+            }),
+            [c, b, a] => {
+                let discriminant = b
+                    .checked_mul(b)
+                    .zip(Rational::from(4).checked_mul(a).and_then(|d| d.checked_mul(c)))
+                    .and_then(|(b2, ac)| b2.checked_sub(ac));
+                self.record(format!(
+                    "Compute the discriminant: Δ = b² - 4ac = ({b})² - 4 * ({a}) * ({c}) = {}",
+                    discriminant.map_or_else(
+                        || solution.discriminant.unwrap_or_default().to_string(),
+                        |discriminant| discriminant.to_string()
+                    )
+                ));
+                self.record(match solution.nature {
+                    Nature::Repeated => format!(
+                        "Δ = 0, so the only root is {x} = -b / 2a = -({b}) / (2 * {a})"
+                    ),
+                    Nature::ComplexPairs => format!(
+                        "Δ < 0, so {x} = (-b ± i√-Δ) / 2a = (-({b}) ± i√-Δ) / (2 * {a})"
+                    ),
+                    _ => format!("Δ > 0, so {x} = (-b ± √Δ) / 2a = (-({b}) ± √Δ) / (2 * {a})"),
+                });
+            }
+            [_, _, _, _] => {
+                self.record_discriminant(solution);
+                self.record(match solution.nature {
+                    Nature::DistinctReal => format!(
+                        "Δ > 0: depress the cubic with {x} = t - b / 3a and apply the trigonometric method to its three real roots"
+                    ),
+                    Nature::Repeated => format!(
+                        "Δ = 0: depress the cubic with {x} = t - b / 3a, whose repeated root is -3q / 2p"
+                    ),
+                    _ => format!(
+                        "Δ < 0: depress the cubic with {x} = t - b / 3a and apply Cardano's formula"
+                    ),
+                });
+            }
+            [_, _, _, _, _] => {
+                self.record_discriminant(solution);
+                self.record(format!("Depress the quartic with {x} = u - b / 4a and apply Ferrari's method through its resolvent cubic"));
+            }
+            _ => self.record(format!(
+                "No closed form exists for degree {}, so approximate the roots with the Aberth–Ehrlich iteration",
+                solution.degree
+            )),
+        }
+        Ok(())
+    }
+
+    fn record_discriminant(&mut self, solution: &Solution) {
+        if let Some(discriminant) = solution.discriminant {
+            self.record(format!("Compute the discriminant: Δ = {discriminant:.6}"));
+        }
+    }
+}
+
+impl Display for Steps {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (number, step) in self.0.iter().enumerate() {
+            writeln!(f, "Step {}: {}", number + 1, step)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::polynomial::Term;
+
+    fn polynomial(coefficients: &[i128]) -> Polynomial {
+        let mut polynomial = Polynomial::default();
+        for (exponent, coefficient) in coefficients.iter().enumerate() {
+            polynomial.push(Term {
+                is_sign_negative: *coefficient < 0,
+                coefficient: Some(Rational::from(coefficient.abs())),
+                operator: Some('*'),
+                identifier: Some("X".to_owned()),
+                exponent: Some(exponent as f64),
+                factors: vec![],
+            });
+        }
+        polynomial
+    }
+
+    #[test]
+    fn test_numbering() {
+        let mut steps = Steps::default();
+        steps.record("first".to_owned());
+        steps.record("second".to_owned());
+        assert_eq!("Step 1: first\nStep 2: second\n", steps.to_string());
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let polynomial = polynomial(&[-2, 0, 1]);
+        let solution = polynomial.solve(&Strategy::ClosedForm).unwrap();
+        let mut steps = Steps::default();
+        steps
+            .record_solution(&polynomial, &solution, &Strategy::ClosedForm)
+            .unwrap();
+        assert_eq!(
+            "Step 1: Compute the discriminant: Δ = b² - 4ac = (0)² - 4 * (1) * (-2) = 8\n\
+             Step 2: Δ > 0, so X = (-b ± √Δ) / 2a = (-(0) ± √Δ) / (2 * 1)\n",
+            steps.to_string()
+        );
+    }
+}
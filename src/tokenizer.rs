@@ -17,7 +17,7 @@ pub enum Token {
 #[derive(Default)]
 pub struct Tokenizer;
 
-impl<'a> Tokenizer {
+impl Tokenizer {
     pub fn new() -> Self {
         Tokenizer {}
     }
@@ -65,7 +65,13 @@ impl<'a> Tokenizer {
                 '(' | ')' => tokenlist.push(Parenthesis(c)),
                 '[' | ']' => tokenlist.push(Bracket(c)),
                 ';' => tokenlist.push(Semicolon),
-                '+' | '-' | '*' | '/' | '%' | '^' | '=' => tokenlist.push(Operator(c)),
+                '+' | '-' | '*' | '/' | '%' | '^' | '=' | '≤' | '≥' => tokenlist.push(Operator(c)),
+                // `<=` and `>=` become the single operators `≤` and `≥`.
+                '<' | '>' => match lexer.next_if_eq(&'=') {
+                    Some(_) if c == '<' => tokenlist.push(Operator('≤')),
+                    Some(_) => tokenlist.push(Operator('≥')),
+                    None => tokenlist.push(Operator(c)),
+                },
                 'i' if !is_alphabetical(lexer.peek().copied())  => tokenlist.push(Imaginary),
                 'A'..='Z' | 'a'..='z' => tokenlist.push(Identifier(self.get_identifier(&mut lexer, c)?)),
                 '0'..='9' => tokenlist.push(Number(self.get_number(&mut lexer, c)?)),
@@ -107,6 +113,18 @@ mod tests {
         assert_eq!(None, tokens.next());
     }
 
+    #[test]
+    fn test_relations() {
+        let mut tokens = get_tokens("< <= > >= ≤ ≥").into_iter();
+        assert_eq!(Some(Token::Operator('<')), tokens.next());
+        assert_eq!(Some(Token::Operator('≤')), tokens.next());
+        assert_eq!(Some(Token::Operator('>')), tokens.next());
+        assert_eq!(Some(Token::Operator('≥')), tokens.next());
+        assert_eq!(Some(Token::Operator('≤')), tokens.next());
+        assert_eq!(Some(Token::Operator('≥')), tokens.next());
+        assert_eq!(None, tokens.next());
+    }
+
     #[test]
     fn test_parenthesis_brackets_and_semicolon() {
         let mut tokens = get_tokens("()[];").into_iter();
@@ -0,0 +1,215 @@
+use super::complex::Complex;
+use super::polynomial::Term;
+use super::rational::Rational;
+use crate::solver;
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+
+/// Polynomials of at most this degree are always stored densely.
+const DENSE_DEGREE: usize = 64;
+/// Above `DENSE_DEGREE`, a dense vector may hold at most this many slots per non-zero
+/// coefficient.
+const SPARSE_RATIO: usize = 8;
+
+/// Exact coefficients of a univariate polynomial indexed by exponent, summed from its terms in
+/// one pass. A vector holds every coefficient up to the degree, while a map keeps only the
+/// non-zero ones when most of that vector would be zeros, as in `X^1000 - 1`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Coefficients {
+    Dense(Vec<Rational>),
+    Sparse(BTreeMap<usize, Rational>),
+}
+
+/// Exponent of the first identifier of a term, 1 for a bare identifier and 0 for a constant.
+/// Fails unless it is a natural number, which makes the term non-polynomial.
+pub(crate) fn exponent(term: &Term) -> Result<usize> {
+    match term
+        .exponent
+        .unwrap_or(if term.identifier.is_some() { 1.0 } else { 0.0 })
+    {
+        exponent if exponent.fract() != 0.0 => {
+            Err(anyhow!("{}{}", "NON_INTEGER_EXPONENT_ERR", term))
+        }
+        exponent if exponent < 0.0 => Err(anyhow!("{}{}", "NEGATIVE_EXPONENT_ERR", term)),
+        exponent => Ok(exponent as usize),
+    }
+}
+
+impl Coefficients {
+    /// Fails on a non-polynomial term, on a term in another identifier than the first one or
+    /// in several identifiers, or when a sum overflows. Vanishing leading coefficients are
+    /// dropped, so that the zero polynomial has no coefficient.
+    pub fn new<'a>(terms: impl IntoIterator<Item = &'a Term> + Clone) -> Result<Self> {
+        let (mut count, mut degree) = (0, 0);
+        let mut unknown: Option<&str> = None;
+        for term in terms.clone() {
+            count += 1;
+            degree = degree.max(exponent(term)?);
+            if term.factors.iter().any(|(_, exponent)| *exponent != 0.0) {
+                return Err(anyhow!("{}{}", "MULTIVARIATE_TERM_ERR", term));
+            }
+            match (unknown, term.identifier.as_deref()) {
+                (Some(a), Some(b)) if a != b => {
+                    return Err(anyhow!("{}{}, {}", "MULTIPLE_UNKNOWNS_ERR", a, b))
+                }
+                (None, identifier) => unknown = identifier,
+                _ => {}
+            }
+        }
+        let mut coefficients = if degree <= DENSE_DEGREE || degree / SPARSE_RATIO < count {
+            let mut dense = vec![Rational::ZERO; degree + 1];
+            for term in terms {
+                let slot = &mut dense[exponent(term)?];
+                *slot = slot
+                    .checked_add(term.coefficient())
+                    .context("RATIONAL_OVERFLOW_ERR")?;
+            }
+            Coefficients::Dense(dense)
+        } else {
+            let mut sparse = BTreeMap::new();
+            for term in terms {
+                let slot = sparse.entry(exponent(term)?).or_insert(Rational::ZERO);
+                *slot = slot
+                    .checked_add(term.coefficient())
+                    .context("RATIONAL_OVERFLOW_ERR")?;
+            }
+            Coefficients::Sparse(sparse)
+        };
+        match &mut coefficients {
+            Coefficients::Dense(dense) => {
+                while dense.last().is_some_and(Rational::is_zero) {
+                    dense.pop();
+                }
+            }
+            Coefficients::Sparse(sparse) => sparse.retain(|_, c| !c.is_zero()),
+        }
+        Ok(coefficients)
+    }
+
+    /// Highest exponent with a non-zero coefficient, 0 for the zero polynomial.
+    pub fn degree(&self) -> usize {
+        match self {
+            Coefficients::Dense(dense) => dense.len().saturating_sub(1),
+            Coefficients::Sparse(sparse) => sparse.keys().next_back().copied().unwrap_or(0),
+        }
+    }
+
+    pub fn get(&self, exponent: usize) -> Rational {
+        match self {
+            Coefficients::Dense(dense) => dense.get(exponent).copied(),
+            Coefficients::Sparse(sparse) => sparse.get(&exponent).copied(),
+        }
+        .unwrap_or(Rational::ZERO)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Coefficients::Dense(dense) => dense.is_empty(),
+            Coefficients::Sparse(sparse) => sparse.is_empty(),
+        }
+    }
+
+    /// Non-zero coefficients with their exponents, in increasing order.
+    pub fn iter(&self) -> Box<dyn DoubleEndedIterator<Item = (usize, Rational)> + '_> {
+        match self {
+            Coefficients::Dense(dense) => Box::new(
+                dense
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .filter(|(_, c)| !c.is_zero()),
+            ),
+            Coefficients::Sparse(sparse) => Box::new(sparse.iter().map(|(e, c)| (*e, *c))),
+        }
+    }
+
+    /// Every coefficient up to the degree, the zero polynomial having the single coefficient 0.
+    pub fn to_dense(&self) -> Vec<Rational> {
+        let mut dense = vec![Rational::ZERO; self.degree() + 1];
+        for (exponent, coefficient) in self.iter() {
+            dense[exponent] = coefficient;
+        }
+        dense
+    }
+
+    /// Horner evaluation in floating point over the non-zero coefficients only, see
+    /// `solver::evaluate_terms`.
+    pub fn evaluate(&self, z: Complex) -> Complex {
+        solver::evaluate_terms(
+            self.iter()
+                .rev()
+                .map(|(exponent, coefficient)| (exponent, f64::from(coefficient))),
+            z,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(coefficient: i128, exponent: f64) -> Term {
+        Term {
+            is_sign_negative: coefficient < 0,
+            coefficient: Some(Rational::from(coefficient.abs())),
+            operator: Some('*'),
+            identifier: Some("X".to_owned()),
+            exponent: Some(exponent),
+            factors: vec![],
+        }
+    }
+
+    #[test]
+    fn test_dense() {
+        // Three like terms sum to one coefficient, and the cancelled leading one is dropped.
+        let terms = [
+            term(1, 1.0),
+            term(2, 0.0),
+            term(1, 1.0),
+            term(3, 2.0),
+            term(-1, 1.0),
+            term(-3, 2.0),
+        ];
+        let coefficients = Coefficients::new(&terms).unwrap();
+        assert_eq!(
+            Coefficients::Dense(vec![Rational::from(2), Rational::ONE]),
+            coefficients
+        );
+        assert_eq!(1, coefficients.degree());
+        assert!(Coefficients::new(&[term(1, 3.0), term(-1, 3.0)])
+            .unwrap()
+            .is_zero());
+        assert!(Coefficients::new(&[term(1, 2.5)]).is_err());
+        assert!(Coefficients::new(&[term(1, -1.0)]).is_err());
+
+        let mut other = term(1, 2.0);
+        other.identifier = Some("Y".to_owned());
+        assert!(Coefficients::new(&[term(1, 1.0), other.clone()]).is_err());
+        other.identifier = Some("X".to_owned());
+        other.factors = vec![("Y".to_owned(), 1.0)];
+        assert!(Coefficients::new(&[other]).is_err());
+    }
+
+    #[test]
+    fn test_sparse() {
+        let terms = [term(1, 1000.0), term(-1, 0.0)];
+        let coefficients = Coefficients::new(&terms).unwrap();
+        assert!(matches!(coefficients, Coefficients::Sparse(_)));
+        assert_eq!(1000, coefficients.degree());
+        assert_eq!(Rational::from(-1), coefficients.get(0));
+        assert_eq!(Rational::ZERO, coefficients.get(500));
+        assert_eq!(
+            vec![(0, Rational::from(-1)), (1000, Rational::ONE)],
+            coefficients.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(1001, coefficients.to_dense().len());
+        assert_eq!(
+            Complex::default(),
+            coefficients.evaluate(Complex::from(-1.0))
+        );
+        assert_eq!(
+            Complex::from(2f64.powi(1000) - 1.0),
+            coefficients.evaluate(Complex::from(2.0))
+        );
+    }
+}
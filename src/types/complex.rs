@@ -0,0 +1,171 @@
+use std::{
+    fmt::{Display, Formatter},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    pub fn is_real(&self) -> bool {
+        self.im == 0.0
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Principal square root, so that a negative real yields a positive imaginary part.
+    pub fn sqrt(&self) -> Self {
+        if self.is_real() {
+            return if self.re.is_sign_negative() {
+                Complex::new(0.0, (-self.re).sqrt())
+            } else {
+                Complex::new(self.re.sqrt(), 0.0)
+            };
+        }
+        let norm = self.norm();
+        let re = ((norm + self.re) / 2.0).sqrt();
+        let im = ((norm - self.re) / 2.0).sqrt();
+        Complex::new(re, if self.im.is_sign_negative() { -im } else { im })
+    }
+
+    /// Natural power by repeated squaring.
+    pub fn powi(self, n: usize) -> Self {
+        match n {
+            0 => Complex::from(1.0),
+            1 => self,
+            _ => {
+                let half = self.powi(n / 2);
+                if n % 2 == 1 {
+                    half * half * self
+                } else {
+                    half * half
+                }
+            }
+        }
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Complex::new(re, 0.0)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let denominator = rhs.re * rhs.re + rhs.im * rhs.im;
+        Complex::new(
+            (self.re * rhs.re + self.im * rhs.im) / denominator,
+            (self.im * rhs.re - self.re * rhs.im) / denominator,
+        )
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision();
+        let write_part = |f: &mut Formatter<'_>, value: f64| match precision {
+            // Avoid printing `-0` for parts that merely carry the sign of a zero.
+            Some(precision) => write!(f, "{:.*}", precision, value + 0.0),
+            None => write!(f, "{}", value + 0.0),
+        };
+
+        if self.is_real() {
+            return write_part(f, self.re);
+        }
+        if self.re != 0.0 {
+            write_part(f, self.re)?;
+            write!(
+                f,
+                " {} ",
+                if self.im.is_sign_negative() { '-' } else { '+' }
+            )?;
+            write_part(f, self.im.abs())?;
+        } else {
+            write_part(f, self.im)?;
+        }
+        write!(f, "i")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -1.0);
+        assert_eq!(Complex::new(4.0, 1.0), a + b);
+        assert_eq!(Complex::new(-2.0, 3.0), a - b);
+        assert_eq!(Complex::new(5.0, 5.0), a * b);
+        assert_eq!(Complex::new(0.1, 0.7), a / b);
+        assert_eq!(Complex::new(-1.0, -2.0), -a);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(Complex::new(0.0, 2.0), Complex::from(-4.0).sqrt());
+        assert_eq!(Complex::new(3.0, 0.0), Complex::from(9.0).sqrt());
+        assert_eq!(Complex::new(1.0, 1.0), Complex::new(0.0, 2.0).sqrt());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("-0.5 + 1.3i", Complex::new(-0.5, 1.3).to_string());
+        assert_eq!("-0.5 - 1.3i", Complex::new(-0.5, -1.3).to_string());
+        assert_eq!("2i", Complex::new(0.0, 2.0).to_string());
+        assert_eq!("4", Complex::new(4.0, 0.0).to_string());
+        assert_eq!(
+            "0.333333 + 0.500000i",
+            format!("{:.6}", Complex::new(1.0 / 3.0, 0.5))
+        );
+        assert_eq!("0.000000", format!("{:.6}", Complex::new(-0.0, 0.0)));
+    }
+}
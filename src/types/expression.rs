@@ -0,0 +1,194 @@
+use super::rational::Rational;
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Product of parameters with their exponents, sorted by name. The empty product is 1.
+pub type Monomial = Vec<(String, u32)>;
+
+/// Polynomial in symbolic parameters with exact coefficients, such as `b^2 - 4 * a * c`.
+/// Only non-zero coefficients are stored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Expression(BTreeMap<Monomial, Rational>);
+
+impl Expression {
+    pub fn constant(value: Rational) -> Self {
+        Expression::term(vec![], value)
+    }
+
+    pub fn term(monomial: Monomial, coefficient: Rational) -> Self {
+        let mut expression = Expression::default();
+        if !coefficient.is_zero() {
+            expression.0.insert(monomial, coefficient);
+        }
+        expression
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Value of an expression free of parameters.
+    pub fn as_constant(&self) -> Option<Rational> {
+        // The empty monomial sorts first, so a constant part alone is not enough.
+        match self.0.iter().collect_vec().as_slice() {
+            [] => Some(Rational::ZERO),
+            [(monomial, coefficient)] if monomial.is_empty() => Some(**coefficient),
+            _ => None,
+        }
+    }
+
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        let mut sum = self.clone();
+        for (monomial, coefficient) in &rhs.0 {
+            let total = sum
+                .0
+                .get(monomial)
+                .copied()
+                .unwrap_or_default()
+                .checked_add(*coefficient)?;
+            if total.is_zero() {
+                sum.0.remove(monomial);
+            } else {
+                sum.0.insert(monomial.clone(), total);
+            }
+        }
+        Some(sum)
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        let mut product = Expression::default();
+        for ((a, x), (b, y)) in self.0.iter().cartesian_product(rhs.0.iter()) {
+            let monomial = a
+                .iter()
+                .chain(b)
+                .cloned()
+                .sorted()
+                .coalesce(|a, b| {
+                    if a.0 == b.0 {
+                        Ok((a.0, a.1 + b.1))
+                    } else {
+                        Err((a, b))
+                    }
+                })
+                .collect();
+            product = product.checked_add(&Expression::term(monomial, x.checked_mul(*y)?))?;
+        }
+        Some(product)
+    }
+
+    pub fn checked_scale(&self, factor: Rational) -> Option<Self> {
+        self.checked_mul(&Expression::constant(factor))
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        self.checked_scale(-Rational::ONE)
+    }
+
+    /// Whether the expression reads unambiguously next to an operator: a constant or a single
+    /// parameter power, possibly negated.
+    pub fn is_simple(&self) -> bool {
+        match self.0.iter().collect_vec().as_slice() {
+            [] => true,
+            [(monomial, coefficient)] => {
+                monomial.is_empty() || (monomial.len() == 1 && coefficient.abs() == Rational::ONE)
+            }
+            _ => false,
+        }
+    }
+
+    /// The expression, within parentheses unless it is simple.
+    pub fn grouped(&self) -> String {
+        if self.is_simple() {
+            self.to_string()
+        } else {
+            format!("({self})")
+        }
+    }
+}
+
+impl Display for Expression {
+    /// Writes the terms by decreasing degree, starting with a positive one when there is one.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let degree = |monomial: &Monomial| monomial.iter().map(|(_, e)| *e).sum::<u32>();
+        let mut terms = self
+            .0
+            .iter()
+            .sorted_by_key(|(monomial, _)| std::cmp::Reverse(degree(monomial)))
+            .collect_vec();
+        if let Some(positive) = terms.iter().position(|(_, c)| !c.is_negative()) {
+            let term = terms.remove(positive);
+            terms.insert(0, term);
+        }
+        for (i, (monomial, coefficient)) in terms.into_iter().enumerate() {
+            match (i, coefficient.is_negative()) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            let mut factors = vec![];
+            if monomial.is_empty() || coefficient.abs() != Rational::ONE {
+                factors.push(format!("{:#}", coefficient.abs()));
+            }
+            for (parameter, exponent) in monomial {
+                factors.push(match exponent {
+                    1 => parameter.clone(),
+                    _ => format!("{parameter}^{exponent}"),
+                });
+            }
+            write!(f, "{}", factors.join(" * "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameter(name: &str) -> Expression {
+        Expression::term(vec![(name.to_owned(), 1)], Rational::ONE)
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let (a, b, c) = (parameter("a"), parameter("b"), parameter("c"));
+        let discriminant = b
+            .checked_mul(&b)
+            .unwrap()
+            .checked_add(
+                &a.checked_mul(&c)
+                    .unwrap()
+                    .checked_scale(Rational::from(-4))
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!("b^2 - 4 * a * c", discriminant.to_string());
+        assert_eq!("(b^2 - 4 * a * c)", discriminant.grouped());
+        assert!(a.checked_add(&a.checked_neg().unwrap()).unwrap().is_zero());
+    }
+
+    #[test]
+    fn test_display() {
+        let sum = parameter("a")
+            .checked_neg()
+            .unwrap()
+            .checked_add(&Expression::constant(Rational::new(1, 2)))
+            .unwrap();
+        assert_eq!("1/2 - a", sum.to_string());
+        assert_eq!("-b", parameter("b").checked_neg().unwrap().grouped());
+        assert_eq!(
+            Some(Rational::from(3)),
+            Expression::constant(Rational::from(3)).as_constant()
+        );
+        assert_eq!(None, parameter("a").as_constant());
+        let sum = parameter("a")
+            .checked_add(&Expression::constant(Rational::ONE))
+            .unwrap();
+        assert_eq!(None, sum.as_constant());
+    }
+}
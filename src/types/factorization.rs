@@ -0,0 +1,624 @@
+use super::polynomial::{divide, integer_coefficients, rational_roots, square_free, trim};
+use super::rational::{gcd, Rational};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+/// Largest number of combinations of modular factors tried when recombining them.
+const MAX_CANDIDATES: usize = 100_000;
+/// Largest modulus of the Hensel lifting, which keeps products of two residues within `i128`.
+const MAX_MODULUS: i128 = 1 << 62;
+/// Number of suitable primes compared to find the one with the fewest modular factors.
+const PRIMES_TRIED: usize = 5;
+
+/// Irreducible factor with integer coefficients, indexed by exponent, whose content is 1 and
+/// whose leading coefficient is positive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Factor {
+    pub coefficients: Vec<Rational>,
+    pub multiplicity: usize,
+}
+
+/// A polynomial written as `content * factor1^m1 * factor2^m2 * ...`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Factorization {
+    pub content: Rational,
+    pub factors: Vec<Factor>,
+    pub identifier: String,
+    /// Whether every factor is known to be irreducible, which fails when one was too costly
+    /// to split.
+    pub complete: bool,
+}
+
+impl Factorization {
+    /// Splits off the rational roots with their multiplicities, then the square-free parts of
+    /// what remains, then the factors of higher degree that Zassenhaus' method finds. `None`
+    /// when a coefficient overflows.
+    pub fn new(coefficients: &[Rational], identifier: &str) -> Option<Self> {
+        let coefficients = trim(coefficients.to_vec());
+        let mut factors = vec![];
+        let mut complete = true;
+        if coefficients.len() > 1 {
+            let mut remaining = primitive(&coefficients)?;
+            for root in rational_roots(&remaining) {
+                let linear = primitive(&[-root, Rational::ONE])?;
+                let mut multiplicity = 0;
+                while let Some((quotient, remainder)) = divide(&remaining, &linear) {
+                    if !remainder.is_empty() {
+                        break;
+                    }
+                    remaining = primitive(&quotient)?;
+                    multiplicity += 1;
+                }
+                factors.push(Factor {
+                    coefficients: linear,
+                    multiplicity,
+                });
+            }
+            let integers = remaining.iter().map(Rational::numerator).collect_vec();
+            // Euclid's algorithm overflows on many coprime inputs of high degree, so it is
+            // skipped when a prime already shows the polynomial to be square-free.
+            let parts = if remaining.len() < 2 {
+                vec![]
+            } else if primes().any(|p| square_free_mod(&integers, p)) {
+                vec![(remaining, 1)]
+            } else {
+                square_free(&remaining)?
+            };
+            for (part, multiplicity) in parts {
+                let (irreducible, split) = zassenhaus(&primitive(&part)?)?;
+                complete &= split;
+                for coefficients in irreducible {
+                    factors.push(Factor {
+                        coefficients,
+                        multiplicity,
+                    });
+                }
+            }
+        }
+        factors.sort_by(|a, b| {
+            let root = |factor: &Factor| match *factor.coefficients.as_slice() {
+                [b, a] => Some(-b / a),
+                _ => None,
+            };
+            (a.coefficients.len(), root(a), &a.coefficients).cmp(&(
+                b.coefficients.len(),
+                root(b),
+                &b.coefficients,
+            ))
+        });
+
+        let leading = coefficients.last().copied().unwrap_or_default();
+        let content = factors.iter().try_fold(leading, |content, factor| {
+            let leading = factor.coefficients.last()?;
+            content.checked_div(leading.checked_pow(factor.multiplicity as u32)?)
+        })?;
+        Some(Factorization {
+            content,
+            factors,
+            identifier: identifier.to_owned(),
+            complete,
+        })
+    }
+}
+
+/// Integer multiple of the polynomial whose coefficients are coprime, with a positive leading
+/// coefficient.
+fn primitive(coefficients: &[Rational]) -> Option<Vec<Rational>> {
+    let integers = integer_coefficients(coefficients)?;
+    let mut divisor = integers
+        .iter()
+        .fold(0, |divisor, c| gcd(divisor, *c))
+        .max(1);
+    if integers.last().is_some_and(|leading| *leading < 0) {
+        divisor = -divisor;
+    }
+    Some(
+        integers
+            .into_iter()
+            .map(|c| Rational::from(c / divisor))
+            .collect(),
+    )
+}
+
+/// Zassenhaus' method on a primitive square-free polynomial: factor it modulo a small prime,
+/// lift that factorization with Hensel's lemma modulo a power of the prime exceeding twice
+/// the Mignotte bound on the coefficients of a factor, then recombine the lifted factors into
+/// the integer ones. The flag is `false` when the modulus or the number of combinations is
+/// too large, in which case the remaining part is kept whole.
+fn zassenhaus(coefficients: &[Rational]) -> Option<(Vec<Vec<Rational>>, bool)> {
+    let integers = coefficients.iter().map(Rational::numerator).collect_vec();
+    let (prime, factors) = match modular_factors(&integers) {
+        Some((_, factors)) if factors.len() == 1 => {
+            return Some((vec![coefficients.to_vec()], true))
+        }
+        Some(modular) => modular,
+        None => return Some((vec![coefficients.to_vec()], false)),
+    };
+    let exponent = match lifting_exponent(&integers, prime) {
+        Some(exponent) => exponent,
+        None => return Some((vec![coefficients.to_vec()], false)),
+    };
+    let modulus = prime.pow(exponent);
+    let mut lifted = hensel_lift(&integers, &factors, prime, exponent)?;
+
+    let mut remaining = coefficients.to_vec();
+    let mut irreducible = vec![];
+    let mut tried = 0;
+    let mut size = 1;
+    'search: while 2 * size <= lifted.len() {
+        for subset in (0..lifted.len()).combinations(size) {
+            tried += 1;
+            if tried > MAX_CANDIDATES {
+                irreducible.push(remaining);
+                return Some((irreducible, false));
+            }
+            let leading = residues(&[remaining.last()?.numerator()], modulus);
+            let product = subset.iter().fold(leading, |product, &i| {
+                mul_mod(&product, &lifted[i], modulus)
+            });
+            // Coefficients of a true factor lie within half the modulus of zero.
+            let candidate = product
+                .iter()
+                .map(|c| Rational::from(if 2 * c > modulus { c - modulus } else { *c }))
+                .collect_vec();
+            let candidate = primitive(&candidate)?;
+            if let Some((quotient, remainder)) = divide(&remaining, &candidate) {
+                if remainder.is_empty() && quotient.iter().all(Rational::is_integer) {
+                    irreducible.push(candidate);
+                    remaining = quotient;
+                    lifted = (0..lifted.len())
+                        .filter(|i| !subset.contains(i))
+                        .map(|i| lifted[i].clone())
+                        .collect();
+                    continue 'search;
+                }
+            }
+        }
+        size += 1;
+    }
+    irreducible.push(remaining);
+    Some((irreducible, true))
+}
+
+/// Monic irreducible factors of the polynomial modulo the prime, below 1000, that does not
+/// divide its leading coefficient, keeps it square-free and gives the fewest factors among
+/// the first suitable ones.
+fn modular_factors(integers: &[i128]) -> Option<(i128, Vec<Vec<i128>>)> {
+    let mut best: Option<(i128, Vec<Vec<i128>>)> = None;
+    let mut tried = 0;
+    for prime in primes().filter(|p| square_free_mod(integers, *p)) {
+        let factors = berlekamp(&monic_mod(&residues(integers, prime), prime)?, prime)?;
+        if best
+            .as_ref()
+            .is_none_or(|(_, best)| factors.len() < best.len())
+        {
+            best = Some((prime, factors));
+        }
+        tried += 1;
+        if tried == PRIMES_TRIED {
+            break;
+        }
+    }
+    best
+}
+
+/// Primes below 1000, in increasing order.
+fn primes() -> impl Iterator<Item = i128> {
+    (2..1000).filter(|n: &i128| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+}
+
+/// Whether the polynomial keeps its degree and stays square-free modulo the prime, in which
+/// case it is square-free over the rationals too.
+fn square_free_mod(integers: &[i128], prime: i128) -> bool {
+    let reduced = residues(integers, prime);
+    let derivative = (1..reduced.len())
+        .map(|i| reduced[i] * i as i128)
+        .collect_vec();
+    reduced.len() == integers.len()
+        && gcd_mod(&reduced, &residues(&derivative, prime), prime).is_some_and(|g| g.len() == 1)
+}
+
+/// Smallest `k` such that `prime^k` exceeds twice the Mignotte bound scaled by the leading
+/// coefficient, or `None` when that power exceeds `MAX_MODULUS`.
+fn lifting_exponent(integers: &[i128], prime: i128) -> Option<u32> {
+    let degree = integers.len() - 1;
+    let largest = integers
+        .iter()
+        .map(|c| (*c as f64).abs())
+        .fold(0.0, f64::max);
+    let leading = (*integers.last()? as f64).abs();
+    let bound = 2.0 * leading * ((degree + 1) as f64).sqrt() * 2f64.powi(degree as i32) * largest;
+    let mut modulus = prime;
+    let mut exponent = 1;
+    while modulus as f64 <= bound {
+        modulus = modulus.checked_mul(prime).filter(|m| *m <= MAX_MODULUS)?;
+        exponent += 1;
+    }
+    Some(exponent)
+}
+
+/// Berlekamp's algorithm on a monic square-free polynomial modulo a prime: the polynomials `v`
+/// with `v^p ≡ v` modulo it form a space whose dimension is its number of irreducible factors,
+/// and `gcd(f, v - s)` for every residue `s` splits it along them.
+fn berlekamp(monic: &[i128], prime: i128) -> Option<Vec<Vec<i128>>> {
+    let degree = monic.len() - 1;
+    let frobenius = pow_mod(&[0, 1], prime, monic, prime)?;
+    // Row i of Q holds x^(ip) mod f, and the null space of the transpose of Q - I holds the
+    // coefficients of v.
+    let mut rows = vec![];
+    let mut power = vec![1];
+    for _ in 0..degree {
+        let mut row = power.clone();
+        row.resize(degree, 0);
+        rows.push(row);
+        power = div_rem_mod(&mul_mod(&power, &frobenius, prime), monic, prime)?.1;
+    }
+    let matrix = (0..degree)
+        .map(|j| {
+            (0..degree)
+                .map(|i| (rows[i][j] - i128::from(i == j)).rem_euclid(prime))
+                .collect()
+        })
+        .collect();
+
+    let mut factors = vec![monic.to_vec()];
+    for v in null_space(matrix, prime)? {
+        let v = residues(&v, prime);
+        if v.len() <= 1 {
+            continue;
+        }
+        let mut split = vec![];
+        for mut factor in factors {
+            for s in 0..prime {
+                let shifted = sub_mod(&v, &[s], prime);
+                let divisor = gcd_mod(&factor, &shifted, prime)?;
+                if divisor.len() > 1 && divisor.len() < factor.len() {
+                    factor = div_rem_mod(&factor, &divisor, prime)?.0;
+                    split.push(divisor);
+                }
+            }
+            split.push(factor);
+        }
+        factors = split;
+    }
+    Some(factors)
+}
+
+/// Basis of the vectors the square matrix maps to zero modulo the prime.
+fn null_space(mut matrix: Vec<Vec<i128>>, prime: i128) -> Option<Vec<Vec<i128>>> {
+    let size = matrix.len();
+    let mut pivots = vec![];
+    for column in 0..size {
+        let row = pivots.len();
+        let pivot = match (row..size).find(|r| matrix[*r][column] != 0) {
+            Some(pivot) => pivot,
+            None => continue,
+        };
+        matrix.swap(row, pivot);
+        let inverse = inverse(matrix[row][column], prime)?;
+        let normalized = matrix[row]
+            .iter()
+            .map(|c| c * inverse % prime)
+            .collect_vec();
+        for (r, line) in matrix.iter_mut().enumerate() {
+            let factor = line[column];
+            if r != row && factor != 0 {
+                for (c, n) in line.iter_mut().zip(&normalized) {
+                    *c = (*c - factor * n).rem_euclid(prime);
+                }
+            }
+        }
+        matrix[row] = normalized;
+        pivots.push(column);
+    }
+    let basis = (0..size)
+        .filter(|column| !pivots.contains(column))
+        .map(|free| {
+            let mut vector = vec![0; size];
+            vector[free] = 1;
+            for (row, pivot) in pivots.iter().enumerate() {
+                vector[*pivot] = (-matrix[row][free]).rem_euclid(prime);
+            }
+            vector
+        })
+        .collect();
+    Some(basis)
+}
+
+/// Lifts `f ≡ lc(f) * g1 * ... * gr (mod p)`, with monic factors, to monic factors modulo
+/// `p^k`, splitting off one factor at a time.
+fn hensel_lift(
+    integers: &[i128],
+    factors: &[Vec<i128>],
+    prime: i128,
+    exponent: u32,
+) -> Option<Vec<Vec<i128>>> {
+    let modulus = prime.pow(exponent);
+    match factors {
+        [] => Some(vec![]),
+        [_] => Some(vec![monic_mod(&residues(integers, modulus), modulus)?]),
+        [first, rest @ ..] => {
+            let leading = residues(&[*integers.last()?], prime);
+            let cofactor = rest.iter().fold(leading, |cofactor, factor| {
+                mul_mod(&cofactor, factor, prime)
+            });
+            let (first, cofactor) = lift_pair(integers, first, &cofactor, prime, exponent)?;
+            let mut lifted = vec![first];
+            lifted.extend(hensel_lift(&cofactor, rest, prime, exponent)?);
+            Some(lifted)
+        }
+    }
+}
+
+/// Linear Hensel lifting of `f ≡ g * h (mod p)`, with `g` monic and coprime to `h`, to
+/// `f ≡ G * H (mod p^k)` with `G` monic: each step solves `σg + τh ≡ (f - GH) / p^j (mod p)`
+/// with `deg τ < deg g` and adds `p^j * τ` to `G` and `p^j * σ` to `H`.
+fn lift_pair(
+    integers: &[i128],
+    g: &[i128],
+    h: &[i128],
+    prime: i128,
+    exponent: u32,
+) -> Option<(Vec<i128>, Vec<i128>)> {
+    let (_, s, t) = extended_gcd_mod(g, h, prime)?;
+    let (mut lifted_g, mut lifted_h) = (g.to_vec(), h.to_vec());
+    let mut modulus = prime;
+    for _ in 1..exponent {
+        let next = modulus * prime;
+        let error = sub_mod(
+            &residues(integers, next),
+            &mul_mod(&lifted_g, &lifted_h, next),
+            next,
+        );
+        let error = error.iter().map(|c| c / modulus).collect_vec();
+        let (quotient, tau) = div_rem_mod(&mul_mod(&t, &error, prime), g, prime)?;
+        let sigma = add_mod(
+            &mul_mod(&s, &error, prime),
+            &mul_mod(&quotient, h, prime),
+            prime,
+        );
+        let scale = |p: &[i128]| p.iter().map(|c| c * modulus).collect_vec();
+        lifted_g = add_mod(&lifted_g, &scale(&tau), next);
+        lifted_h = add_mod(&lifted_h, &scale(&sigma), next);
+        modulus = next;
+    }
+    Some((lifted_g, lifted_h))
+}
+
+/// Coefficients reduced into `0..modulus`, without vanishing leading ones. The helpers below
+/// take such residues, whose products stay within `i128` below `MAX_MODULUS`.
+fn residues(integers: &[i128], modulus: i128) -> Vec<i128> {
+    let mut residues = integers.iter().map(|c| c.rem_euclid(modulus)).collect_vec();
+    while residues.last() == Some(&0) {
+        residues.pop();
+    }
+    residues
+}
+
+fn add_mod(a: &[i128], b: &[i128], modulus: i128) -> Vec<i128> {
+    let sum = (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0) + b.get(i).unwrap_or(&0))
+        .collect_vec();
+    residues(&sum, modulus)
+}
+
+fn sub_mod(a: &[i128], b: &[i128], modulus: i128) -> Vec<i128> {
+    let difference = (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0) - b.get(i).unwrap_or(&0))
+        .collect_vec();
+    residues(&difference, modulus)
+}
+
+fn mul_mod(a: &[i128], b: &[i128], modulus: i128) -> Vec<i128> {
+    let mut product = vec![0; (a.len() + b.len()).saturating_sub(1)];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            product[i + j] = (product[i + j] + a * b % modulus) % modulus;
+        }
+    }
+    residues(&product, modulus)
+}
+
+/// Inverse of `a` modulo `modulus`, when they are coprime.
+fn inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (mut r0, mut r1) = (a.rem_euclid(modulus), modulus);
+    let (mut s0, mut s1) = (1, 0);
+    while r1 != 0 {
+        let quotient = r0 / r1;
+        (r0, r1) = (r1, r0 - quotient * r1);
+        (s0, s1) = (s1, s0 - quotient * s1);
+    }
+    (r0 == 1).then(|| s0.rem_euclid(modulus))
+}
+
+fn monic_mod(a: &[i128], modulus: i128) -> Option<Vec<i128>> {
+    let inverse = inverse(*a.last()?, modulus)?;
+    Some(mul_mod(a, &[inverse], modulus))
+}
+
+/// Quotient and remainder modulo `modulus`, whose divisor has an invertible leading
+/// coefficient.
+fn div_rem_mod(
+    dividend: &[i128],
+    divisor: &[i128],
+    modulus: i128,
+) -> Option<(Vec<i128>, Vec<i128>)> {
+    let divisor = residues(divisor, modulus);
+    let inverse = inverse(*divisor.last()?, modulus)?;
+    let mut remainder = residues(dividend, modulus);
+    if remainder.len() < divisor.len() {
+        return Some((vec![], remainder));
+    }
+    let mut quotient = vec![0; remainder.len() - divisor.len() + 1];
+    for shift in (0..quotient.len()).rev() {
+        let factor = remainder[shift + divisor.len() - 1] * inverse % modulus;
+        for (i, c) in divisor.iter().enumerate() {
+            remainder[shift + i] =
+                (remainder[shift + i] - c * factor % modulus).rem_euclid(modulus);
+        }
+        quotient[shift] = factor;
+    }
+    Some((residues(&quotient, modulus), residues(&remainder, modulus)))
+}
+
+/// Monic greatest common divisor modulo a prime.
+fn gcd_mod(a: &[i128], b: &[i128], prime: i128) -> Option<Vec<i128>> {
+    let (mut a, mut b) = (residues(a, prime), residues(b, prime));
+    while !b.is_empty() {
+        let remainder = div_rem_mod(&a, &b, prime)?.1;
+        a = std::mem::replace(&mut b, remainder);
+    }
+    monic_mod(&a, prime)
+}
+
+/// Monic `gcd(a, b)` modulo a prime, with `s` and `t` such that `s * a + t * b` equals it.
+fn extended_gcd_mod(
+    a: &[i128],
+    b: &[i128],
+    prime: i128,
+) -> Option<(Vec<i128>, Vec<i128>, Vec<i128>)> {
+    let (mut r0, mut r1) = (residues(a, prime), residues(b, prime));
+    let (mut s0, mut s1) = (vec![1], vec![]);
+    let (mut t0, mut t1) = (vec![], vec![1]);
+    while !r1.is_empty() {
+        let (quotient, remainder) = div_rem_mod(&r0, &r1, prime)?;
+        let s = sub_mod(&s0, &mul_mod(&quotient, &s1, prime), prime);
+        let t = sub_mod(&t0, &mul_mod(&quotient, &t1, prime), prime);
+        (r0, r1) = (r1, remainder);
+        (s0, s1) = (s1, s);
+        (t0, t1) = (t1, t);
+    }
+    let inverse = [inverse(*r0.last()?, prime)?];
+    Some((
+        mul_mod(&r0, &inverse, prime),
+        mul_mod(&s0, &inverse, prime),
+        mul_mod(&t0, &inverse, prime),
+    ))
+}
+
+/// `base^exponent` modulo the monic polynomial and the prime, by repeated squaring.
+fn pow_mod(base: &[i128], mut exponent: i128, monic: &[i128], prime: i128) -> Option<Vec<i128>> {
+    let mut base = div_rem_mod(base, monic, prime)?.1;
+    let mut power = vec![1];
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            power = div_rem_mod(&mul_mod(&power, &base, prime), monic, prime)?.1;
+        }
+        base = div_rem_mod(&mul_mod(&base, &base, prime), monic, prime)?.1;
+        exponent /= 2;
+    }
+    Some(power)
+}
+
+impl Factorization {
+    fn write_factor(&self, f: &mut Formatter<'_>, factor: &Factor) -> std::fmt::Result {
+        let terms = factor
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero())
+            .collect_vec();
+        let parenthesized = terms.len() > 1;
+        if parenthesized {
+            write!(f, "(")?;
+        }
+        for (i, (exponent, coefficient)) in terms.into_iter().enumerate() {
+            match (i, coefficient.is_negative()) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            if coefficient.abs() != Rational::ONE || exponent == 0 {
+                write!(f, "{}", coefficient.abs())?;
+            }
+            match exponent {
+                0 => {}
+                1 => write!(f, "{}", self.identifier)?,
+                _ => write!(f, "{}^{}", self.identifier, exponent)?,
+            }
+        }
+        if parenthesized {
+            write!(f, ")")?;
+        }
+        if factor.multiplicity > 1 {
+            write!(f, "^{}", factor.multiplicity)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Factorization {
+    /// Writes e.g. `(X - 1)(X - 2)(X - 3)`, `-2X(X^2 + 1)` or `(1/2)(X - 1)^2`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.factors.is_empty() {
+            return write!(f, "{:#}", self.content);
+        }
+        match self.content {
+            content if content == Rational::ONE => {}
+            content if content == -Rational::ONE => write!(f, "-")?,
+            content if content.is_integer() => write!(f, "{content}")?,
+            content => write!(f, "({content:#})")?,
+        }
+        for factor in &self.factors {
+            self.write_factor(f, factor)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn factor(integers: &[i128]) -> String {
+        let coefficients = integers.iter().copied().map(Rational::from).collect_vec();
+        Factorization::new(&coefficients, "X").unwrap().to_string()
+    }
+
+    #[test]
+    fn test_rational_roots() {
+        assert_eq!("(X - 1)(X - 2)(X - 3)", factor(&[-6, 11, -6, 1]));
+        assert_eq!("2(X + 1)(X - 1)", factor(&[-2, 0, 2]));
+        assert_eq!("X(X - 1)^2", factor(&[0, 1, -2, 1]));
+        assert_eq!("-(2X - 1)", factor(&[1, -2]));
+    }
+
+    #[test]
+    fn test_zassenhaus() {
+        assert_eq!("(X^2 - 2X + 2)(X^2 + 2X + 2)", factor(&[4, 0, 0, 0, 1]));
+        assert_eq!("(X^2 + 1)^2(X^2 + X + 1)", factor(&[1, 1, 3, 2, 3, 1, 1]));
+        assert_eq!("(X^4 + 1)", factor(&[1, 0, 0, 0, 1]));
+        assert_eq!(
+            "(X^2 + 1000)(X^2 + 1001)",
+            factor(&[1001000, 0, 2001, 0, 1])
+        );
+        assert_eq!("(2X^2 + 3)(3X^3 - X + 5)", factor(&[15, -3, 10, 7, 0, 6]));
+    }
+
+    #[test]
+    fn test_incomplete() {
+        // (X^6 + 1000000007)(X^6 + 1000000009) needs a lifting modulus above `MAX_MODULUS`.
+        let mut integers = vec![0; 13];
+        integers[0] = 1_000_000_007 * 1_000_000_009;
+        integers[6] = 2_000_000_016;
+        integers[12] = 1;
+        let coefficients = integers.iter().copied().map(Rational::from).collect_vec();
+        let factorization = Factorization::new(&coefficients, "X").unwrap();
+        assert!(!factorization.complete);
+        assert!(
+            Factorization::new(&[Rational::from(2), Rational::ONE], "X")
+                .unwrap()
+                .complete
+        );
+    }
+
+    #[test]
+    fn test_content() {
+        let coefficients = [Rational::new(-1, 2), Rational::ZERO, Rational::new(1, 2)];
+        let factorization = Factorization::new(&coefficients, "Y").unwrap();
+        assert_eq!("(1/2)(Y + 1)(Y - 1)", factorization.to_string());
+        assert_eq!("7", factor(&[7]));
+    }
+}
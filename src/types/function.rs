@@ -13,9 +13,10 @@ impl Type for Function {
     //     "#00A0B0"
     // }
 
-    fn into_term(&self) -> Term {
-        let mut term = Term::default();
-        term.identifier = Some(self.identifier.clone());
-        term
+    fn to_term(&self) -> Term {
+        Term {
+            identifier: Some(self.identifier.clone()),
+            ..Default::default()
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,183 @@
+use super::rational::Rational;
+use itertools::Itertools;
+use std::{
+    fmt::{Display, Formatter},
+    ops::{Index, IndexMut},
+};
+
+/// Dense matrix of exact fractions, stored row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    columns: usize,
+    entries: Vec<Rational>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, columns: usize) -> Self {
+        Matrix {
+            rows,
+            columns,
+            entries: vec![Rational::ZERO; rows * columns],
+        }
+    }
+
+    /// Panics when the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<Rational>>) -> Self {
+        let columns = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == columns),
+            "MATRIX_SHAPE_ERR"
+        );
+        Matrix {
+            rows: rows.len(),
+            columns,
+            entries: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn row(&self, row: usize) -> &[Rational] {
+        &self.entries[row * self.columns..(row + 1) * self.columns]
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        for column in 0..self.columns {
+            self.entries
+                .swap(a * self.columns + column, b * self.columns + column);
+        }
+    }
+
+    /// Brings the first `columns` columns to reduced row echelon form by Gauss-Jordan
+    /// elimination, carrying the remaining columns along, and returns the pivot columns.
+    /// Each pivot is the entry of largest magnitude left in its column, as partial pivoting
+    /// keeps the intermediate fractions small. `None` when an entry overflows.
+    pub fn row_reduce(&mut self, columns: usize) -> Option<Vec<usize>> {
+        let mut pivots = vec![];
+        for column in 0..columns.min(self.columns) {
+            let row = pivots.len();
+            if row == self.rows {
+                break;
+            }
+            let pivot = (row..self.rows)
+                .max_by_key(|r| self[(*r, column)].abs())
+                .unwrap();
+            if self[(pivot, column)].is_zero() {
+                continue;
+            }
+            self.swap_rows(row, pivot);
+
+            let scale = self[(row, column)];
+            for c in 0..self.columns {
+                self[(row, c)] = self[(row, c)].checked_div(scale)?;
+            }
+            for other in (0..self.rows).filter(|other| *other != row) {
+                let factor = self[(other, column)];
+                if factor.is_zero() {
+                    continue;
+                }
+                for c in 0..self.columns {
+                    let product = factor.checked_mul(self[(row, c)])?;
+                    self[(other, c)] = self[(other, c)].checked_sub(product)?;
+                }
+            }
+            pivots.push(column);
+        }
+        Some(pivots)
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = Rational;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        &self.entries[row * self.columns + column]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        &mut self.entries[row * self.columns + column]
+    }
+}
+
+impl Display for Matrix {
+    /// Writes one bracketed row per line, with the entries of each column aligned.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let cells = self
+            .entries
+            .iter()
+            .map(|entry| format!("{entry:#}"))
+            .collect_vec();
+        let widths = (0..self.columns)
+            .map(|column| {
+                (0..self.rows)
+                    .map(|row| cells[row * self.columns + column].chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect_vec();
+        for row in 0..self.rows {
+            let entries = (0..self.columns)
+                .map(|column| {
+                    format!(
+                        "{:>width$}",
+                        cells[row * self.columns + column],
+                        width = widths[column]
+                    )
+                })
+                .join(" ");
+            writeln!(f, "[ {entries} ]")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(rows: &[&[i128]]) -> Matrix {
+        Matrix::from_rows(
+            rows.iter()
+                .map(|row| row.iter().copied().map(Rational::from).collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_row_reduce() {
+        let mut m = matrix(&[&[1, 1, 3], &[2, -1, 0]]);
+        assert_eq!(Some(vec![0, 1]), m.row_reduce(2));
+        assert_eq!(matrix(&[&[1, 0, 1], &[0, 1, 2]]), m);
+
+        let mut m = matrix(&[&[1, 2, 3], &[2, 4, 6]]);
+        assert_eq!(Some(vec![0]), m.row_reduce(2));
+        assert_eq!(matrix(&[&[1, 2, 3], &[0, 0, 0]]), m);
+    }
+
+    #[test]
+    fn test_partial_pivoting() {
+        let mut m = matrix(&[&[1, 1], &[-3, 6]]);
+        m.row_reduce(1);
+        // The entry of largest magnitude, -3, becomes the first pivot.
+        assert_eq!(&[Rational::ONE, Rational::from(-2)], m.row(0));
+    }
+
+    #[test]
+    fn test_display() {
+        let m = Matrix::from_rows(vec![
+            vec![Rational::ONE, Rational::new(-1, 2)],
+            vec![Rational::from(10), Rational::ZERO],
+        ]);
+        assert_eq!("[  1 -1/2 ]\n[ 10    0 ]\n", m.to_string());
+        assert_eq!(Rational::ZERO, Matrix::zeros(2, 3)[(1, 2)]);
+    }
+}
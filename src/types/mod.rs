@@ -1,7 +1,13 @@
 pub mod polynomial;
 pub mod matrix;
+pub mod coefficients;
 pub mod complex;
+pub mod expression;
+pub mod factorization;
 pub mod rational;
+pub mod rational_function;
+pub mod sturm;
+pub mod surd;
 pub mod function;
 pub mod variable;
 
@@ -16,7 +22,7 @@ pub trait Type: Debug + Display {
         "#FFFFFF"
     }
 
-    fn into_term(&self) -> Term;
+    fn to_term(&self) -> Term;
 
     fn into_node_object(self) -> NodeObject where Self: Sized + 'static {
         NodeObject::Operand(Rc::new(self))
@@ -1,53 +1,183 @@
+use super::coefficients::{exponent, Coefficients};
+use super::complex::Complex;
+use super::expression::Expression;
+use super::factorization::Factorization;
+use super::rational::{lcm, Rational};
+use super::sturm::SturmSequence;
+use super::surd::Surd;
 use crate::node::{Branch, NodeObject};
+use crate::solver::{self, Numeric, Relation, Solution, SolutionSet, Strategy};
 use crate::tokenizer::Token::*;
-use derive_more::{Deref, DerefMut};
+use anyhow::{anyhow, Context, Error, Result};
 use itertools::Itertools;
 use merge::Merge;
-use std::{cmp::Ordering::*, fmt::Display, ops::Add};
+use std::{
+    cell::OnceCell,
+    cmp::Ordering::*,
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    fmt::Display,
+    ops::{Add, Deref, DerefMut, Div, Mul, Neg, Rem, Sub},
+};
 
 #[derive(Debug, Default, Merge, PartialEq, Clone)]
 pub struct Term {
     #[merge(skip)]
     pub is_sign_negative: bool,
-    pub coefficient: Option<f64>,
+    pub coefficient: Option<Rational>,
     pub operator: Option<char>,
     pub identifier: Option<String>,
     pub exponent: Option<f64>,
+    /// Further identifiers of a product with their exponents, e.g. `a` in `X^2 * a`. Together
+    /// with `identifier` they are sorted by name, so that like terms compare equal.
+    #[merge(skip)]
+    pub factors: Vec<(String, f64)>,
 }
 
 impl Term {
-    pub fn coefficient(&self) -> f64 {
+    pub fn coefficient(&self) -> Rational {
         if self.is_sign_negative {
-            -self.coefficient.unwrap()
+            -self.coefficient.unwrap_or(Rational::ONE)
         } else {
-            self.coefficient.unwrap()
+            self.coefficient.unwrap_or(Rational::ONE)
         }
     }
-}
 
-impl Add for Term {
-    type Output = Self;
+    /// Every identifier of the term with its exponent, a bare identifier having exponent 1.
+    pub fn monomial(&self) -> Vec<(String, f64)> {
+        self.identifier
+            .iter()
+            .map(|identifier| (identifier.clone(), self.exponent.unwrap_or(1.0)))
+            .chain(self.factors.iter().cloned())
+            .collect()
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        let coefficient = match (self.is_sign_negative, rhs.is_sign_negative) {
-            (true, true) => -self.coefficient.unwrap_or(1.0) - rhs.coefficient.unwrap_or(1.0),
-            (true, false) => -self.coefficient.unwrap_or(1.0) + rhs.coefficient.unwrap_or(1.0),
-            (false, true) => self.coefficient.unwrap_or(1.0) - rhs.coefficient.unwrap_or(1.0),
-            (false, false) => self.coefficient.unwrap_or(1.0) + rhs.coefficient.unwrap_or(1.0),
+    /// Sum of the exponents of its identifiers, 0 for a constant.
+    pub fn degree(&self) -> f64 {
+        self.monomial().iter().map(|(_, exponent)| exponent).sum()
+    }
+
+    /// Stores a product of identifiers in canonical order, adding up the exponents of repeated
+    /// ones.
+    fn set_monomial(&mut self, monomial: Vec<(String, f64)>) {
+        let mut monomial = monomial
+            .into_iter()
+            .sorted_by(|a, b| a.0.cmp(&b.0))
+            .coalesce(|a, b| {
+                if a.0 == b.0 {
+                    Ok((a.0, a.1 + b.1))
+                } else {
+                    Err((a, b))
+                }
+            });
+        let (identifier, exponent) = monomial.next().unzip();
+        self.identifier = identifier;
+        self.exponent = exponent;
+        self.factors = monomial.collect();
+    }
+
+    /// Product of the coefficients and of the identifiers, which must not be combined by an
+    /// operator other than `*`. Fails when the coefficient overflows.
+    fn product(&self, other: &Term) -> Result<Term> {
+        let coefficient = self
+            .coefficient()
+            .checked_mul(other.coefficient())
+            .context("RATIONAL_OVERFLOW_ERR")?;
+        let mut term = Term {
+            is_sign_negative: coefficient.is_negative(),
+            coefficient: Some(coefficient.abs()),
+            operator: Some('*'),
+            ..Default::default()
+        };
+        term.set_monomial([self.monomial(), other.monomial()].concat());
+        Ok(term)
+    }
+
+    /// `term^exponent`, raising the coefficient exactly and multiplying the exponent of every
+    /// identifier. Fails when a coefficient other than 1 has a non-integer exponent, or on
+    /// overflow.
+    fn power(&self, exponent: f64) -> Result<Term> {
+        let mut term = self.clone();
+        let coefficient = self.coefficient();
+        if coefficient != Rational::ONE {
+            if exponent.fract() != 0.0 || exponent.abs() > u32::MAX as f64 {
+                return Err(anyhow!(
+                    "{}{}^{}",
+                    "NON_INTEGER_EXPONENT_ERR",
+                    self,
+                    exponent
+                ));
+            }
+            let mut power = coefficient
+                .checked_pow(exponent.abs() as u32)
+                .context("RATIONAL_OVERFLOW_ERR")?;
+            if exponent < 0.0 {
+                power = power.checked_recip().context("DIVISION_BY_ZERO_ERR")?;
+            }
+            term.is_sign_negative = power.is_negative();
+            term.coefficient = Some(power.abs());
+        }
+        if term.identifier.is_some() {
+            term.set_monomial(
+                self.monomial()
+                    .into_iter()
+                    .map(|(identifier, power)| (identifier, power * exponent))
+                    .collect(),
+            );
+        }
+        Ok(term)
+    }
+
+    /// `1 / term`, or `None` when the term is zero or keeps an operator other than `*`.
+    fn reciprocal(&self) -> Option<Term> {
+        if !matches!(self.operator, None | Some('*')) {
+            return None;
+        }
+        let coefficient = self.coefficient().checked_recip()?;
+        let mut term = Term {
+            is_sign_negative: coefficient.is_negative(),
+            coefficient: Some(coefficient.abs()),
+            operator: Some('*'),
+            ..Default::default()
         };
+        term.set_monomial(
+            self.monomial()
+                .into_iter()
+                .map(|(identifier, exponent)| (identifier, -exponent))
+                .collect(),
+        );
+        Some(term)
+    }
+
+    /// Multiplies the term by `identifier^power`.
+    fn multiply_by(&mut self, identifier: &str, power: f64) {
+        let mut monomial = self.monomial();
+        monomial.push((identifier.to_owned(), power));
+        if self.coefficient.is_some() && self.operator.is_none() {
+            self.operator = Some('*');
+        }
+        self.set_monomial(monomial);
+    }
+}
+
+impl Term {
+    /// Sum of like terms, keeping the writing of the first one, or `None` when the
+    /// coefficient overflows.
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        let coefficient = self.coefficient().checked_add(rhs.coefficient())?;
         let operator = if self.operator.is_none() && rhs.operator.is_none() {
             Some('*')
         } else {
             self.operator
         };
-        Term {
-            is_sign_negative: coefficient.is_sign_negative(),
+        Some(Term {
+            is_sign_negative: coefficient.is_negative(),
             coefficient: Some(coefficient.abs()),
-            // operator: self.operator,
             operator,
             identifier: self.identifier,
             exponent: self.exponent,
-        }
+            factors: self.factors,
+        })
     }
 }
 
@@ -56,7 +186,10 @@ impl PartialOrd for Term {
         self.exponent
             .partial_cmp(&other.exponent)
             .and_then(|ord| match ord {
-                Equal => self.identifier.partial_cmp(&other.identifier),
+                Equal => match self.identifier.partial_cmp(&other.identifier) {
+                    Some(Equal) => self.factors.partial_cmp(&other.factors),
+                    ord => ord,
+                },
                 // .and_then(|ord| match ord {
                 //     Equal => self.coefficient.partial_cmp(&other.coefficient),
                 //     _ => Some(ord),
@@ -66,33 +199,89 @@ impl PartialOrd for Term {
     }
 }
 
-impl From<Branch> for Term {
-    fn from(branch: Branch) -> Self {
+impl TryFrom<Branch> for Term {
+    type Error = Error;
+
+    /// Fails when folding constant factors overflows.
+    fn try_from(branch: Branch) -> Result<Self> {
         let node = branch.borrow().clone();
         let mut term = Term::default();
 
         match node.object {
-            NodeObject::Operator(Operator('-')) | NodeObject::Operator(Operator('+')) => panic!(),
+            NodeObject::Operator(Operator(operator @ ('-' | '+'))) => {
+                return Err(anyhow!("{}{}", "UNEXP_OPERATOR_ERR", operator))
+            }
             NodeObject::Operator(Operator(operator)) => match operator {
                 '^' => {
-                    term.merge(Term::from(node.left.unwrap()));
-                    if let Some(right) = node.right {
-                        let right_node = right.borrow().clone();
-                        match right_node.object.into() {
-                            // TODO: Make this generic ot not?
-                            Number(exponent) => term.exponent = Some(exponent),
-                            _ => unimplemented!(),
-                        }
+                    let base = Term::try_from(node.left.context("UNEXP_END_ERR")?)?;
+                    let right = node.right.context("UNEXP_END_ERR")?.borrow().clone();
+                    match right.object.into() {
+                        Number(exponent) => term = base.power(exponent)?,
+                        token => return Err(anyhow!("{}{:?}", "UNEXP_TOKEN_ERR", token)),
                     }
                 }
                 '*' | '/' | '%' => {
-                    term.operator = Some(operator);
-                    term.merge(Term::from(node.left.unwrap()));
-                    term.merge(Term::from(node.right.unwrap()));
+                    let left = Term::try_from(node.left.context("UNEXP_END_ERR")?)?;
+                    let right = Term::try_from(node.right.context("UNEXP_END_ERR")?)?;
+                    if operator != '*' && right.coefficient == Some(Rational::ZERO) {
+                        return Err(anyhow!("DIVISION_BY_ZERO_ERR"));
+                    }
+                    let divisor = right.coefficient.unwrap_or(Rational::ONE);
+                    // Dividing by a product of identifiers multiplies by their negative powers.
+                    if operator == '/'
+                        && right.identifier.is_some()
+                        && matches!(right.operator, None | Some('*'))
+                        && !divisor.is_zero()
+                    {
+                        let coefficient = left
+                            .coefficient
+                            .unwrap_or(Rational::ONE)
+                            .checked_div(divisor)
+                            .context("RATIONAL_OVERFLOW_ERR")?;
+                        term.is_sign_negative = left.is_sign_negative ^ right.is_sign_negative;
+                        term.coefficient = Some(coefficient);
+                        term.operator = Some('*');
+                        let reciprocal = right
+                            .monomial()
+                            .into_iter()
+                            .map(|(identifier, exponent)| (identifier, -exponent));
+                        term.set_monomial(left.monomial().into_iter().chain(reciprocal).collect());
+                        return Ok(term);
+                    }
+                    // Constant factors are folded exactly; anything else keeps the operator.
+                    let coefficient = match (operator, left.coefficient, right.coefficient) {
+                        ('*', Some(a), Some(b)) => Some(a.checked_mul(b)),
+                        ('/', a, Some(b)) if right.identifier.is_none() && !b.is_zero() => {
+                            Some(a.unwrap_or(Rational::ONE).checked_div(b))
+                        }
+                        _ => None,
+                    };
+                    match coefficient {
+                        Some(coefficient) => {
+                            term.coefficient = Some(coefficient.context("RATIONAL_OVERFLOW_ERR")?);
+                            if left.identifier.is_some() || right.identifier.is_some() {
+                                term.operator = Some('*');
+                            }
+                        }
+                        None => term.operator = Some(operator),
+                    }
+                    // A product of identifiers becomes one multivariate term.
+                    let monomial = match (&left.identifier, &right.identifier) {
+                        (Some(_), Some(_)) => Some([left.monomial(), right.monomial()].concat()),
+                        _ => None,
+                    };
+                    term.factors = [left.factors.clone(), right.factors.clone()].concat();
+                    // Merging skips the signs, which multiply.
+                    term.is_sign_negative = left.is_sign_negative ^ right.is_sign_negative;
+                    term.merge(left);
+                    term.merge(right);
+                    if let Some(monomial) = monomial {
+                        term.set_monomial(monomial);
+                    }
                 }
-                _ => panic!(),
+                _ => return Err(anyhow!("{}{}", "UNEXP_OPERATOR_ERR", operator)),
             },
-            NodeObject::Operand(operand) => term.merge(operand.into_term()),
+            NodeObject::Operand(operand) => term = operand.to_term(),
             // Identifier(identifier) => term.identifier = Some(identifier),
             // Number(coefficient) => {
             //     if coefficient.is_sign_negative() {
@@ -104,128 +293,1010 @@ impl From<Branch> for Term {
             // }
             _ => unreachable!(),
         }
-        term
+        Ok(term)
     }
 }
 
 impl Display for Term {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = vec![];
         if let Some(coefficient) = self.coefficient {
-            write!(f, "{coefficient} ")?;
-        }
-        if let Some(operator) = self.operator {
-            write!(f, "{operator} ")?;
+            parts.push(coefficient.to_string());
         }
         if let Some(identifier) = &self.identifier {
-            write!(f, "{identifier}")?;
+            if let (Some(operator), false) = (self.operator, parts.is_empty()) {
+                parts.push(operator.to_string());
+            }
+            let mut identifiers = vec![match self.exponent {
+                Some(exponent) => format!("{identifier}^{exponent}"),
+                None => identifier.clone(),
+            }];
+            for (identifier, exponent) in &self.factors {
+                identifiers.push(match exponent {
+                    exponent if *exponent == 1.0 => identifier.clone(),
+                    exponent => format!("{identifier}^{exponent}"),
+                });
+            }
+            parts.push(identifiers.join(" * "));
         }
-        if let Some(exponent) = self.exponent {
-            write!(f, "^{exponent}")?;
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// Largest degree whose coefficients are expanded into a vector for solving, factoring and
+/// the other dense algorithms, which would not finish in reasonable time beyond it.
+pub(crate) const MAX_DENSE_DEGREE: usize = 1000;
+
+/// Sum of terms as written, together with its canonical univariate form.
+#[derive(Debug, Default, Clone)]
+pub struct Polynomial {
+    terms: Vec<Term>,
+    /// Coefficients of the terms, converted on first use and dropped whenever the terms may
+    /// change, see `Polynomial::to_coefficients`.
+    coefficients: OnceCell<Coefficients>,
+}
+
+impl From<Vec<Term>> for Polynomial {
+    fn from(terms: Vec<Term>) -> Self {
+        Polynomial {
+            terms,
+            coefficients: OnceCell::new(),
         }
-        Ok(())
     }
 }
 
-#[derive(Debug, Default, DerefMut, Deref)]
-pub struct Polynomial(Vec<Term>);
+impl Deref for Polynomial {
+    type Target = Vec<Term>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terms
+    }
+}
+
+impl DerefMut for Polynomial {
+    /// Every change of the terms goes through here, so the coefficients converted from them
+    /// are dropped.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.coefficients.take();
+        &mut self.terms
+    }
+}
 
 impl Polynomial {
-    pub fn reduce(&mut self) {
-        self.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
-        let mut dummy = Term::default();
-        dummy.exponent = Some(f64::MAX);
-        self.0.push(dummy);
-
-        let mut pairs = self.iter().tuple_windows::<(&Term, &Term)>();
-        let mut reduced_terms = vec![];
-        while let Some((a, b)) = pairs.next() {
-            match (
-                a.is_sign_negative ^ b.is_sign_negative,
-                a.coefficient.partial_cmp(&b.coefficient),
-                a.operator.partial_cmp(&b.operator),
-                a.identifier.partial_cmp(&b.identifier),
-                a.exponent.partial_cmp(&b.exponent),
-            ) {
-                (true, Some(Equal), Some(Equal), Some(Equal), Some(Equal)) => {
-                    pairs.next();
+    /// Sums like terms, i.e. those raising the same identifiers to the same powers, in order
+    /// of degree. Each sum keeps the writing of the first of its terms, and is dropped when the
+    /// terms cancel out. A lone zero constant is dropped too, while an explicit `0 * X^2` is
+    /// kept. Fails when a sum overflows.
+    pub fn reduce(&mut self) -> Result<()> {
+        match Coefficients::new(self.iter()) {
+            Ok(coefficients) => self.reduce_to(coefficients),
+            Err(_) => self.reduce_monomials(),
+        }
+    }
+
+    /// Rewrites a univariate polynomial with its sums, which `coefficients` already holds
+    /// indexed by exponent, so that the terms are grouped in one pass without sorting them.
+    fn reduce_to(&mut self, coefficients: Coefficients) -> Result<()> {
+        let mut written: BTreeMap<usize, (Term, usize)> = BTreeMap::new();
+        for term in self.drain(..) {
+            let (_, count) = written.entry(exponent(&term)?).or_insert((term, 0));
+            *count += 1;
+        }
+        let terms = written
+            .into_iter()
+            .map(|(exponent, (term, count))| match count {
+                1 => (term, count),
+                _ => {
+                    let sum = coefficients.get(exponent);
+                    let term = Term {
+                        is_sign_negative: sum.is_negative(),
+                        coefficient: Some(sum.abs()),
+                        operator: term.operator.or(Some('*')),
+                        ..term
+                    };
+                    (term, count)
+                }
+            })
+            .filter(|(term, count)| {
+                !term.coefficient().is_zero() || (*count == 1 && term.identifier.is_some())
+            })
+            .map(|(term, _)| term)
+            .collect();
+        *self = Polynomial {
+            terms,
+            coefficients: OnceCell::from(coefficients),
+        };
+        Ok(())
+    }
+
+    /// Groups the terms of any other polynomial by monomial, then orders the sums by degree.
+    fn reduce_monomials(&mut self) -> Result<()> {
+        let mut reduced: Vec<(Term, usize)> = vec![];
+        let mut positions = HashMap::new();
+        for term in self.drain(..) {
+            let key = term
+                .monomial()
+                .into_iter()
+                .filter(|(_, exponent)| *exponent != 0.0)
+                .map(|(identifier, exponent)| (identifier, exponent.to_bits()))
+                .collect::<Vec<_>>();
+            match positions.get(&key) {
+                Some(&i) => {
+                    let (sum, count): &mut (Term, usize) = &mut reduced[i];
+                    *sum = sum
+                        .clone()
+                        .checked_add(term)
+                        .context("RATIONAL_OVERFLOW_ERR")?;
+                    *count += 1;
                 }
-                (_, _, _, Some(Equal), Some(Equal)) => {
-                    reduced_terms.push(a.clone() + b.clone());
-                    pairs.next();
+                None => {
+                    positions.insert(key, reduced.len());
+                    reduced.push((term, 1));
                 }
-                _ => reduced_terms.push(a.clone()),
             }
         }
-        self.0 = reduced_terms;
+        reduced.sort_by(|(a, _), (b, _)| {
+            a.degree()
+                .partial_cmp(&b.degree())
+                .map(|ord| ord.then(a.partial_cmp(b).unwrap_or(Equal)))
+                .unwrap()
+        });
+        **self = reduced
+            .into_iter()
+            .filter(|(term, count)| {
+                !term.coefficient().is_zero() || (*count == 1 && term.identifier.is_some())
+            })
+            .map(|(term, _)| term)
+            .collect();
+        Ok(())
     }
 
-    pub fn degree(&mut self) -> usize {
-        self.iter().fold(0, |degree, current| {
-            degree.max(current.exponent.unwrap() as usize)
-        })
+    /// Expanded product, multiplying every term of one factor by every term of the other.
+    /// Fails when a coefficient overflows.
+    pub fn product(&self, other: &Polynomial) -> Result<Polynomial> {
+        self.iter()
+            .cartesian_product(other.iter())
+            .map(|(a, b)| a.product(b))
+            .collect::<Result<Vec<_>>>()
+            .map(Polynomial::from)
     }
 
-    pub fn solve(&mut self) -> Vec<f64> {
-        let mut terms = self.0.clone();
-        terms.reverse();
+    /// Expanded `n`th power by repeated squaring, reduced after each multiplication so that
+    /// the number of terms stays bounded. Fails when a coefficient overflows.
+    pub fn power(&self, mut n: u32) -> Result<Polynomial> {
+        let mut power = Polynomial::from(vec![Term {
+            coefficient: Some(Rational::ONE),
+            ..Default::default()
+        }]);
+        let mut base = self.clone();
+        while n > 0 {
+            if n % 2 == 1 {
+                power = power.product(&base)?;
+                power.reduce()?;
+            }
+            n /= 2;
+            if n > 0 {
+                base = base.product(&base)?;
+                base.reduce()?;
+            }
+        }
+        Ok(power)
+    }
 
-        let degree = self.degree();
+    /// Euclidean division in the unknown, returning the quotient and the remainder. Fails
+    /// when the divisor is zero or a coefficient overflows.
+    pub fn div_rem(&self, divisor: &Polynomial) -> Result<(Polynomial, Polynomial)> {
+        let identifier = self.shared_identifier(divisor)?;
+        let divisor = trim(divisor.coefficients()?);
+        if divisor.is_empty() {
+            return Err(anyhow!("DIVISION_BY_ZERO_ERR"));
+        }
+        let (quotient, remainder) =
+            divide(&self.coefficients()?, &divisor).context("RATIONAL_OVERFLOW_ERR")?;
+        Ok((
+            Polynomial::from_coefficients(&quotient, identifier),
+            Polynomial::from_coefficients(&remainder, identifier),
+        ))
+    }
+
+    /// Highest exponent with a non-zero coefficient.
+    pub fn degree(&self) -> Result<usize> {
+        Ok(self.to_coefficients()?.degree())
+    }
+
+    /// Exact coefficients of the first identifier of each term, summed in one pass and kept
+    /// until the terms change. Fails on a negative or non-integer exponent, which makes the
+    /// polynomial an equation of another kind, or when a sum overflows.
+    pub fn to_coefficients(&self) -> Result<&Coefficients> {
+        if let Some(coefficients) = self.coefficients.get() {
+            return Ok(coefficients);
+        }
+        let coefficients = Coefficients::new(self.iter())?;
+        Ok(self.coefficients.get_or_init(|| coefficients))
+    }
 
-        let mut coefficients = terms.iter().map(|t| t.coefficient());
-        let a = coefficients.next().unwrap_or_default();
-        let b = coefficients.next().unwrap_or_default();
-        let c = coefficients.next().unwrap_or_default();
-        let _d = coefficients.next().unwrap_or_default();
+    /// Exact coefficients indexed by exponent, with like terms summed and vanishing leading
+    /// coefficients dropped. A bare identifier has exponent 1 and a bare constant exponent 0.
+    /// Fails beyond `MAX_DENSE_DEGREE`, where only the degree and `Polynomial::evaluate` work.
+    pub fn coefficients(&self) -> Result<Vec<Rational>> {
+        let coefficients = self.to_coefficients()?;
+        match coefficients.degree() {
+            degree if degree > MAX_DENSE_DEGREE => Err(anyhow!("{}{}", "DEGREE_LIMIT_ERR", degree)),
+            _ => Ok(coefficients.to_dense()),
+        }
+    }
 
-        match degree {
-            1 => {
-                vec![-(b / a)]
+    fn float_coefficients(&self) -> Result<Vec<f64>> {
+        Ok(self.coefficients()?.into_iter().map(f64::from).collect())
+    }
+
+    /// Discriminant of polynomials of degree 2 to 4, whose sign tells the nature of the roots.
+    pub fn discriminant(&self) -> Option<f64> {
+        match *self.float_coefficients().ok()?.as_slice() {
+            [c, b, a] => Some(b * b - 4. * a * c),
+            [d, c, b, a] => Some(
+                b * b * c * c - 4. * a * c.powi(3) - 4. * b.powi(3) * d - 27. * a * a * d * d
+                    + 18. * a * b * c * d,
+            ),
+            [e, d, c, b, a] => Some(
+                256. * a.powi(3) * e.powi(3) - 192. * a * a * b * d * e * e
+                    - 128. * a * a * c * c * e * e
+                    + 144. * a * a * c * d * d * e
+                    - 27. * a * a * d.powi(4)
+                    + 144. * a * b * b * c * e * e
+                    - 6. * a * b * b * d * d * e
+                    - 80. * a * b * c * c * d * e
+                    + 18. * a * b * c * d.powi(3)
+                    + 16. * a * c.powi(4) * e
+                    - 4. * a * c.powi(3) * d * d
+                    - 27. * b.powi(4) * e * e
+                    + 18. * b.powi(3) * c * d * e
+                    - 4. * b.powi(3) * d.powi(3)
+                    - 4. * b * b * c.powi(3) * e
+                    + b * b * c * c * d * d,
+            ),
+            _ => None,
+        }
+    }
+
+    /// Fails when the numeric solver does not converge.
+    pub fn solve(&self, strategy: &Strategy) -> Result<Solution> {
+        let coefficients = self.coefficients()?;
+        let degree = coefficients.len() - 1;
+        if degree == 0 {
+            return Ok(Solution::constant(f64::from(coefficients[0])));
+        }
+        // Every factor of a square-free decomposition has simple roots, which all methods find
+        // far more accurately than clustered ones. Listing the most repeated roots first keeps
+        // equal values adjacent so that `Solution::from_roots` counts them.
+        let factors = match square_free(&coefficients) {
+            Some(factors) if factors.iter().any(|(_, multiplicity)| *multiplicity > 1) => factors
+                .into_iter()
+                .sorted_by_key(|(_, multiplicity)| std::cmp::Reverse(*multiplicity))
+                .collect_vec(),
+            _ => vec![(coefficients.clone(), 1)],
+        };
+        let rational_roots = rational_roots(&coefficients);
+        let mut values = vec![];
+        for (factor, multiplicity) in &factors {
+            let factor = factor.iter().copied().map(f64::from).collect_vec();
+            for root in roots(&factor, strategy)? {
+                values.extend(std::iter::repeat_n(root, *multiplicity));
+            }
+        }
+        let roots = values
+            .into_iter()
+            .map(|root| {
+                // Snap onto exact rational roots so that repeated ones group reliably.
+                rational_roots
+                    .iter()
+                    .map(|rational| Complex::from(f64::from(*rational)))
+                    .find(|rational| (*rational - root).norm() <= 1e-9 * rational.norm().max(1.0))
+                    .unwrap_or(root)
+            })
+            .collect();
+        let surds = factors
+            .iter()
+            .filter_map(|(factor, _)| match *factor.as_slice() {
+                [c, b, a] => Surd::quadratic_roots(a, b, c),
+                _ => None,
+            })
+            .flatten()
+            .collect_vec();
+        Ok(Solution::from_roots(degree, self.discriminant(), roots)
+            .with_rational_roots(&rational_roots)
+            .with_surds(&surds)
+            .with_residuals(&self.float_coefficients()?))
+    }
+
+    /// Solution set of `self relation 0` over the reals.
+    pub fn solve_inequality(&self, relation: Relation, strategy: &Strategy) -> Result<SolutionSet> {
+        let mut roots = self
+            .solve(strategy)?
+            .roots
+            .into_iter()
+            .filter(|root| root.value.is_real())
+            .collect_vec();
+        roots.sort_by(|a, b| a.value.re.total_cmp(&b.value.re));
+        let coefficients = self.float_coefficients()?;
+        Ok(SolutionSet::new(relation, &roots, |x| {
+            solver::evaluate(&coefficients, Complex::from(x)).re
+        }))
+    }
+
+    /// Horner evaluation in floating point over the non-zero coefficients, see
+    /// `Coefficients::evaluate`.
+    pub fn evaluate(&self, z: Complex) -> Result<Complex> {
+        Ok(self.to_coefficients()?.evaluate(z))
+    }
+
+    /// Polynomial in `identifier` with the given coefficients, indexed by exponent.
+    pub fn from_coefficients(coefficients: &[Rational], identifier: &str) -> Self {
+        let terms = coefficients
+            .iter()
+            .enumerate()
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .map(|(exponent, coefficient)| Term {
+                is_sign_negative: coefficient.is_negative(),
+                coefficient: Some(coefficient.abs()),
+                operator: Some('*'),
+                identifier: Some(identifier.to_owned()),
+                exponent: Some(exponent as f64),
+                factors: vec![],
+            })
+            .collect();
+        Polynomial {
+            terms,
+            coefficients: OnceCell::from(Coefficients::Dense(trim(coefficients.to_vec()))),
+        }
+    }
+
+    /// Name of the unknown, `X` when the polynomial is a constant.
+    pub fn identifier(&self) -> &str {
+        self.iter()
+            .find_map(|term| term.identifier.as_deref())
+            .unwrap_or("X")
+    }
+
+    /// Name of the unknown of both polynomials, `X` when both are constants. Fails when they
+    /// have different unknowns, which no operation on a single variable can combine.
+    pub fn shared_identifier<'a>(&'a self, other: &'a Polynomial) -> Result<&'a str> {
+        let mut identifiers = self
+            .iter()
+            .chain(other.iter())
+            .filter_map(|term| term.identifier.as_deref());
+        let first = identifiers.next().unwrap_or("X");
+        match identifiers.find(|identifier| *identifier != first) {
+            Some(other) => Err(anyhow!("{}{}, {}", "MULTIPLE_UNKNOWNS_ERR", first, other)),
+            None => Ok(first),
+        }
+    }
+
+    /// Multiplies every term by the lowest power of each identifier that leaves no negative
+    /// exponent, raised to an even power when `even` so that the sign of the polynomial is
+    /// kept. Returns those identifiers with their powers, in alphabetical order; the cleared
+    /// polynomial agrees with the original one wherever none of them is zero.
+    pub fn clear_denominators(&mut self, even: bool) -> Vec<(String, f64)> {
+        let mut powers: Vec<(String, f64)> = vec![];
+        for (identifier, exponent) in self.iter().flat_map(Term::monomial) {
+            if exponent >= 0.0 {
+                continue;
+            }
+            match powers.iter_mut().find(|(name, _)| *name == identifier) {
+                Some((_, power)) => *power = power.max(-exponent),
+                None => powers.push((identifier, -exponent)),
+            }
+        }
+        for (_, power) in powers.iter_mut() {
+            if even {
+                *power = 2.0 * (*power / 2.0).ceil();
+            }
+        }
+        powers.sort_by(|a, b| a.0.cmp(&b.0));
+        if !powers.is_empty() {
+            // A zero constant such as the right-hand side of `X^-1 = 0` would otherwise become
+            // a term in the identifiers.
+            self.retain(|term| term.identifier.is_some() || !term.coefficient().is_zero());
+        }
+        for term in self.iter_mut() {
+            for (identifier, power) in &powers {
+                term.multiply_by(identifier, *power);
+            }
+        }
+        powers
+    }
+
+    /// Writes the polynomial in `substitute = identifier^(1/q)`, for the smallest `q` that
+    /// makes every exponent of `identifier` an integer, and returns `q`.
+    pub fn substitute_root(&mut self, identifier: &str, substitute: &str) -> Result<i128> {
+        let mut index = 1;
+        for term in self.iter() {
+            for (name, exponent) in term.monomial() {
+                if name == identifier {
+                    let exponent = rational_exponent(exponent)
+                        .with_context(|| format!("{}{}", "NON_RATIONAL_EXPONENT_ERR", term))?;
+                    index = lcm(index, exponent.denominator()).context("RATIONAL_OVERFLOW_ERR")?;
+                }
+            }
+        }
+        if index > 1 {
+            for term in self.iter_mut() {
+                let monomial = term
+                    .monomial()
+                    .into_iter()
+                    .map(|(name, exponent)| {
+                        if name == identifier {
+                            (substitute.to_owned(), (exponent * index as f64).round())
+                        } else {
+                            (name, exponent)
+                        }
+                    })
+                    .collect();
+                term.set_monomial(monomial);
+            }
+        }
+        Ok(index)
+    }
+
+    /// Every identifier appearing in a term, in alphabetical order.
+    pub fn identifiers(&self) -> Vec<String> {
+        self.iter()
+            .flat_map(Term::monomial)
+            .map(|(identifier, _)| identifier)
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    /// Coefficients of `unknown` indexed by exponent, each a polynomial in the other
+    /// identifiers. Exponents must be non-negative integers.
+    pub fn coefficients_in(&self, unknown: &str) -> Result<Vec<Expression>> {
+        let mut coefficients: Vec<Expression> = vec![];
+        for term in self.iter() {
+            if matches!(term.operator, Some('/') | Some('%')) {
+                return Err(anyhow!("{}{}", "NON_POLYNOMIAL_TERM_ERR", term));
+            }
+            let mut degree = 0;
+            let mut parameters = vec![];
+            for (identifier, exponent) in term.monomial() {
+                if exponent < 0.0 || exponent.fract() != 0.0 {
+                    return Err(anyhow!("{}{}", "NON_POLYNOMIAL_TERM_ERR", term));
+                }
+                match exponent as u32 {
+                    0 => {}
+                    exponent if identifier == unknown => degree = exponent as usize,
+                    exponent => parameters.push((identifier, exponent)),
+                }
+            }
+            if coefficients.len() <= degree {
+                coefficients.resize(degree + 1, Expression::default());
+            }
+            coefficients[degree] = coefficients[degree]
+                .checked_add(&Expression::term(parameters, term.coefficient()))
+                .context("RATIONAL_OVERFLOW_ERR")?;
+        }
+        while coefficients.last().is_some_and(Expression::is_zero) {
+            coefficients.pop();
+        }
+        Ok(coefficients)
+    }
+
+    /// `n`th derivative. Fails when a coefficient overflows.
+    pub fn derivative(&self, n: usize) -> Result<Polynomial> {
+        let mut coefficients = self.coefficients()?;
+        for _ in 0..n {
+            coefficients = derivative(&coefficients).context("RATIONAL_OVERFLOW_ERR")?;
+        }
+        Ok(Polynomial::from_coefficients(
+            &coefficients,
+            self.identifier(),
+        ))
+    }
+
+    /// Antiderivative whose value at 0 is `constant`. Fails when a coefficient overflows.
+    pub fn integral(&self, constant: Rational) -> Result<Polynomial> {
+        let coefficients = std::iter::once(Some(constant))
+            .chain(
+                self.coefficients()?
+                    .iter()
+                    .enumerate()
+                    .map(|(exponent, c)| c.checked_div(Rational::from(exponent as i128 + 1))),
+            )
+            .collect::<Option<Vec<_>>>()
+            .context("RATIONAL_OVERFLOW_ERR")?;
+        Ok(Polynomial::from_coefficients(
+            &coefficients,
+            self.identifier(),
+        ))
+    }
+
+    /// Monic greatest common divisor. Fails when the unknowns differ or a coefficient
+    /// overflows.
+    pub fn gcd(&self, other: &Polynomial) -> Result<Polynomial> {
+        let identifier = self.shared_identifier(other)?;
+        let divisor =
+            gcd(&self.coefficients()?, &other.coefficients()?).context("RATIONAL_OVERFLOW_ERR")?;
+        Ok(Polynomial::from_coefficients(&divisor, identifier))
+    }
+
+    /// Monic greatest common divisor from the subresultant remainder sequence, whose
+    /// coefficients stay integers. Fails when the unknowns differ or a coefficient overflows.
+    pub fn subresultant_gcd(&self, other: &Polynomial) -> Result<Polynomial> {
+        let identifier = self.shared_identifier(other)?;
+        let divisor = subresultant_gcd(&self.coefficients()?, &other.coefficients()?)
+            .context("RATIONAL_OVERFLOW_ERR")?;
+        Ok(Polynomial::from_coefficients(&divisor, identifier))
+    }
+
+    /// Monic greatest common divisor of the floating-point coefficients, indexed by exponent,
+    /// where remainders below `tolerance` relative to the divisor count as zero. Fails when
+    /// the unknowns differ.
+    pub fn approximate_gcd(&self, other: &Polynomial, tolerance: f64) -> Result<Vec<f64>> {
+        self.shared_identifier(other)?;
+        Ok(approximate_gcd(
+            &self.float_coefficients()?,
+            &other.float_coefficients()?,
+            tolerance,
+        ))
+    }
+
+    /// Monic least common multiple, which is zero when either polynomial is. Fails when the
+    /// unknowns differ or a coefficient overflows.
+    pub fn lcm(&self, other: &Polynomial) -> Result<Polynomial> {
+        let identifier = self.shared_identifier(other)?;
+        let (a, b) = (self.coefficients()?, other.coefficients()?);
+        let multiple = || -> Option<Vec<Rational>> {
+            let product = monic(multiply(&a, &b)?)?;
+            Some(match gcd(&a, &b)? {
+                divisor if product.is_empty() => divisor[..0].to_vec(),
+                divisor => divide(&product, &divisor)?.0,
+            })
+        };
+        let multiple = multiple().context("RATIONAL_OVERFLOW_ERR")?;
+        Ok(Polynomial::from_coefficients(&multiple, identifier))
+    }
+
+    /// Yun's square-free decomposition: pairwise coprime factors without repeated roots, each
+    /// with the multiplicity of its roots, whose product is the polynomial up to a constant.
+    /// Fails when a coefficient overflows.
+    pub fn square_free(&self) -> Result<Vec<(Polynomial, usize)>> {
+        let factors = square_free(&self.coefficients()?).context("RATIONAL_OVERFLOW_ERR")?;
+        Ok(factors
+            .into_iter()
+            .map(|(factor, multiplicity)| {
+                (
+                    Polynomial::from_coefficients(&factor, self.identifier()),
+                    multiplicity,
+                )
+            })
+            .collect())
+    }
+
+    /// Distinct rational roots in increasing order.
+    pub fn rational_roots(&self) -> Result<Vec<Rational>> {
+        Ok(rational_roots(&self.coefficients()?))
+    }
+
+    /// Factorization into irreducible polynomials over the rationals. Fails when a
+    /// coefficient overflows.
+    pub fn factor(&self) -> Result<Factorization> {
+        Factorization::new(&self.coefficients()?, self.identifier())
+            .context("FACTORIZATION_OVERFLOW_ERR")
+    }
+
+    /// Sturm sequence of the square-free part. Fails when a coefficient overflows.
+    pub fn sturm_sequence(&self) -> Result<SturmSequence> {
+        SturmSequence::new(&self.coefficients()?).context("RATIONAL_OVERFLOW_ERR")
+    }
+}
+
+/// Largest root index a substitution looks for, e.g. 3 for `X^(2/3)`.
+const MAX_ROOT_INDEX: i128 = 1000;
+
+/// Fraction with the smallest denominator that matches `exponent` up to round-off.
+fn rational_exponent(exponent: f64) -> Option<Rational> {
+    (1..=MAX_ROOT_INDEX).find_map(|q| {
+        let scaled = exponent * q as f64;
+        ((scaled - scaled.round()).abs() < 1e-9).then(|| Rational::new(scaled.round() as i128, q))
+    })
+}
+
+/// By the rational root theorem every rational root is ±p/q, where p divides the constant and
+/// q the leading coefficient of the polynomial scaled to integer coefficients.
+pub(crate) fn rational_roots(coefficients: &[Rational]) -> Vec<Rational> {
+    let mut roots = vec![];
+    if coefficients.len() < 2 {
+        return roots;
+    }
+    // Factor out X^k first so that the constant term does not vanish.
+    let lowest = coefficients.iter().position(|c| !c.is_zero()).unwrap();
+    if lowest > 0 {
+        roots.push(Rational::ZERO);
+    }
+    let coefficients = &coefficients[lowest..];
+    let integers = match integer_coefficients(coefficients) {
+        Some(integers) => integers,
+        None => return roots,
+    };
+    let numerators = divisors(integers[0]);
+    let denominators = divisors(integers[integers.len() - 1]);
+    for (p, q) in numerators.iter().cartesian_product(denominators.iter()) {
+        for candidate in [Rational::new(*p, *q), Rational::new(-*p, *q)] {
+            if !roots.contains(&candidate)
+                && evaluate(coefficients, candidate) == Some(Rational::ZERO)
+            {
+                roots.push(candidate);
             }
-            2 => match (b * b - 4. * a * c).partial_cmp(&0.0) {
-                Some(Greater) => vec![
-                    (-b - f64::sqrt(b * b - 4. * a * c)) / (2.0 * a),
-                    (-b + f64::sqrt(b * b - 4. * a * c)) / (2.0 * a),
-                ],
-                Some(Equal) => vec![-b / (2. * a)],
-                Some(Less) => vec![],
+        }
+    }
+    roots.sort();
+    roots
+}
+
+/// Exact Horner evaluation, or `None` when the intermediate values overflow.
+pub(crate) fn evaluate(coefficients: &[Rational], x: Rational) -> Option<Rational> {
+    coefficients
+        .iter()
+        .rev()
+        .try_fold(Rational::ZERO, |acc, coefficient| {
+            acc.checked_mul(x)?.checked_add(*coefficient)
+        })
+}
+
+/// Roots of a polynomial given by its coefficients, with the closed form of its degree when
+/// there is one.
+fn roots(coefficients: &[f64], strategy: &Strategy) -> Result<Vec<Complex>> {
+    if let Strategy::Numeric(numeric) = strategy {
+        return numeric.roots(coefficients);
+    }
+    Ok(match *coefficients {
+        [b, a] => {
+            vec![Complex::from(-(b / a))]
+        }
+        [c, b, a] => {
+            let discriminant = b * b - 4. * a * c;
+            match discriminant.partial_cmp(&0.0) {
+                Some(Equal) => vec![Complex::from(-b / (2. * a)); 2],
+                Some(_) => {
+                    let root = Complex::from(discriminant).sqrt();
+                    vec![
+                        (Complex::from(-b) - root) / Complex::from(2. * a),
+                        (Complex::from(-b) + root) / Complex::from(2. * a),
+                    ]
+                }
                 None => panic!(),
-            },
-            3 => {
-                todo!();
             }
-            _ => unimplemented!(),
         }
+        [d, c, b, a] => solver::cubic(a, b, c, d),
+        [e, d, c, b, a] => solver::quartic(a, b, c, d, e),
+        _ => return Numeric::default().roots(coefficients),
+    })
+}
+
+/// Drops vanishing leading coefficients, leaving the zero polynomial empty.
+pub(crate) fn trim(mut coefficients: Vec<Rational>) -> Vec<Rational> {
+    while coefficients.last().is_some_and(Rational::is_zero) {
+        coefficients.pop();
+    }
+    coefficients
+}
+
+pub(crate) fn derivative(coefficients: &[Rational]) -> Option<Vec<Rational>> {
+    let derivative = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(exponent, c)| c.checked_mul(Rational::from(exponent as i128)))
+        .collect::<Option<_>>()?;
+    Some(trim(derivative))
+}
+
+pub(crate) fn subtract(a: &[Rational], b: &[Rational]) -> Option<Vec<Rational>> {
+    let difference = (0..a.len().max(b.len()))
+        .map(|i| {
+            let a = a.get(i).copied().unwrap_or_default();
+            a.checked_sub(b.get(i).copied().unwrap_or_default())
+        })
+        .collect::<Option<_>>()?;
+    Some(trim(difference))
+}
+
+pub(crate) fn multiply(a: &[Rational], b: &[Rational]) -> Option<Vec<Rational>> {
+    let mut product = vec![Rational::ZERO; (a.len() + b.len()).saturating_sub(1)];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            product[i + j] = product[i + j].checked_add(a.checked_mul(*b)?)?;
+        }
+    }
+    Some(trim(product))
+}
+
+/// Long division, returning the quotient and the remainder. The divisor must not be zero.
+pub(crate) fn divide(
+    dividend: &[Rational],
+    divisor: &[Rational],
+) -> Option<(Vec<Rational>, Vec<Rational>)> {
+    let divisor = trim(divisor.to_vec());
+    let leading = *divisor.last()?;
+    let mut remainder = trim(dividend.to_vec());
+    if remainder.len() < divisor.len() {
+        return Some((vec![], remainder));
+    }
+    let mut quotient = vec![Rational::ZERO; remainder.len() - divisor.len() + 1];
+    for shift in (0..quotient.len()).rev() {
+        let factor = remainder[shift + divisor.len() - 1].checked_div(leading)?;
+        for (i, coefficient) in divisor.iter().enumerate() {
+            remainder[shift + i] =
+                remainder[shift + i].checked_sub(coefficient.checked_mul(factor)?)?;
+        }
+        quotient[shift] = factor;
+    }
+    Some((trim(quotient), trim(remainder)))
+}
+
+fn monic(coefficients: Vec<Rational>) -> Option<Vec<Rational>> {
+    match coefficients.last().copied() {
+        Some(leading) => coefficients
+            .into_iter()
+            .map(|c| c.checked_div(leading))
+            .collect(),
+        None => Some(coefficients),
+    }
+}
+
+/// Euclid's algorithm. Keeping every remainder monic slows the growth of the fractions.
+pub(crate) fn gcd(a: &[Rational], b: &[Rational]) -> Option<Vec<Rational>> {
+    let (mut a, mut b) = (monic(trim(a.to_vec()))?, monic(trim(b.to_vec()))?);
+    while !b.is_empty() {
+        let (_, remainder) = divide(&a, &b)?;
+        (a, b) = (b, monic(remainder)?);
+    }
+    Some(a)
+}
+
+/// Collins' subresultant algorithm: each pseudo-remainder `lc(b)^(δ + 1) a mod b`, with `δ`
+/// the difference of degrees, is divided by `g h^δ`, a factor it is known to have, which
+/// keeps integer coefficients from growing exponentially.
+pub(crate) fn subresultant_gcd(a: &[Rational], b: &[Rational]) -> Option<Vec<Rational>> {
+    let integers = |coefficients: &[Rational]| -> Option<Vec<Rational>> {
+        Some(
+            integer_coefficients(&trim(coefficients.to_vec()))?
+                .into_iter()
+                .map(Rational::from)
+                .collect(),
+        )
+    };
+    let (mut a, mut b) = (integers(a)?, integers(b)?);
+    if a.len() < b.len() {
+        (a, b) = (b, a);
+    }
+    let (mut g, mut h) = (Rational::ONE, Rational::ONE);
+    while !b.is_empty() {
+        let delta = (a.len() - b.len()) as u32;
+        let scale = b[b.len() - 1].checked_pow(delta + 1)?;
+        let scaled = a
+            .iter()
+            .map(|c| c.checked_mul(scale))
+            .collect::<Option<Vec<_>>>()?;
+        let (_, remainder) = divide(&scaled, &b)?;
+        if remainder.len() == 1 {
+            return Some(vec![Rational::ONE]);
+        }
+        let divisor = g.checked_mul(h.checked_pow(delta)?)?;
+        a = std::mem::replace(
+            &mut b,
+            remainder
+                .into_iter()
+                .map(|c| c.checked_div(divisor))
+                .collect::<Option<_>>()?,
+        );
+        g = a[a.len() - 1];
+        h = match delta {
+            0 => h,
+            _ => g
+                .checked_pow(delta)?
+                .checked_div(h.checked_pow(delta - 1)?)?,
+        };
+    }
+    monic(a)
+}
+
+/// Euclid's algorithm in floating point, where the coefficients of each remainder below
+/// `tolerance` times the largest one of the divisor are rounded to zero.
+pub(crate) fn approximate_gcd(a: &[f64], b: &[f64], tolerance: f64) -> Vec<f64> {
+    let trim = |mut coefficients: Vec<f64>, scale: f64| {
+        for c in coefficients.iter_mut() {
+            if c.abs() <= tolerance * scale {
+                *c = 0.0;
+            }
+        }
+        while coefficients.last() == Some(&0.0) {
+            coefficients.pop();
+        }
+        coefficients
+    };
+    let norm = |coefficients: &[f64]| coefficients.iter().fold(0.0, |m: f64, c| m.max(c.abs()));
+    let (mut a, mut b) = (trim(a.to_vec(), norm(a)), trim(b.to_vec(), norm(b)));
+    while !b.is_empty() {
+        let leading = b[b.len() - 1];
+        let mut remainder = a;
+        while remainder.len() >= b.len() {
+            let factor = remainder[remainder.len() - 1] / leading;
+            let shift = remainder.len() - b.len();
+            for (i, c) in b.iter().enumerate() {
+                remainder[shift + i] -= factor * c;
+            }
+            remainder.pop();
+        }
+        let scale = norm(&b);
+        (a, b) = (b, trim(remainder, scale));
+    }
+    match a.last().copied() {
+        Some(leading) => a.into_iter().map(|c| c / leading).collect(),
+        None => a,
+    }
+}
+
+/// Yun's algorithm: with `a0 = gcd(f, f')`, `b1 = f / a0` and `d1 = f' / a0 - b1'`, each
+/// `ai = gcd(bi, di)` gathers the roots of multiplicity `i`, then `b(i+1) = bi / ai` and
+/// `d(i+1) = di / ai - b(i+1)'`.
+pub(crate) fn square_free(coefficients: &[Rational]) -> Option<Vec<(Vec<Rational>, usize)>> {
+    let coefficients = trim(coefficients.to_vec());
+    let prime = derivative(&coefficients)?;
+    let divisor = gcd(&coefficients, &prime)?;
+    let mut b = divide(&coefficients, &divisor)?.0;
+    let mut d = subtract(&divide(&prime, &divisor)?.0, &derivative(&b)?)?;
+    let mut factors = vec![];
+    let mut multiplicity = 1;
+    while b.len() > 1 {
+        let factor = gcd(&b, &d)?;
+        b = divide(&b, &factor)?.0;
+        d = subtract(&divide(&d, &factor)?.0, &derivative(&b)?)?;
+        if factor.len() > 1 {
+            factors.push((factor, multiplicity));
+        }
+        multiplicity += 1;
+    }
+    Some(factors)
+}
+
+/// Scales the coefficients by the lcm of their denominators.
+pub(crate) fn integer_coefficients(coefficients: &[Rational]) -> Option<Vec<i128>> {
+    let multiple = coefficients
+        .iter()
+        .try_fold(1, |multiple, c| lcm(multiple, c.denominator()))?;
+    coefficients
+        .iter()
+        .map(|c| {
+            c.checked_mul(Rational::from(multiple))
+                .map(|c| c.numerator())
+        })
+        .collect()
+}
+
+/// Largest magnitude whose divisors are enumerated; trial division beyond this is too slow.
+const MAX_DIVISIBLE: u128 = 1_000_000_000_000;
+
+pub(crate) fn divisors(n: i128) -> Vec<i128> {
+    let n = n.unsigned_abs();
+    if n > MAX_DIVISIBLE {
+        return vec![];
+    }
+    let mut divisors = vec![];
+    let mut d = 1;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            divisors.push(d as i128);
+            if d * d != n {
+                divisors.push((n / d) as i128);
+            }
+        }
+        d += 1;
     }
+    divisors
 }
 
-impl From<Branch> for Polynomial {
-    fn from(branch: Branch) -> Self {
+impl TryFrom<Branch> for Polynomial {
+    type Error = Error;
+
+    fn try_from(branch: Branch) -> Result<Self> {
         let node = branch.borrow().clone();
+        let operands = || -> Result<(Polynomial, Polynomial)> {
+            Ok((
+                Polynomial::try_from(node.left.clone().context("UNEXP_END_ERR")?)?,
+                Polynomial::try_from(node.right.clone().context("UNEXP_END_ERR")?)?,
+            ))
+        };
 
         let mut polynomial = Polynomial::default();
-        match node.object.into() {
-            Operator(operator) if operator == '+' || operator == '-' => {
-                polynomial.append(&mut Polynomial::from(node.left.unwrap()));
-                let mut right = Polynomial::from(node.right.unwrap());
+        match node.object {
+            NodeObject::Operator(Operator(operator)) if operator == '+' || operator == '-' => {
+                let (mut left, mut right) = operands()?;
+                polynomial.append(&mut left);
                 if operator == '-' {
-                    right[0].is_sign_negative = true;
-                    // if let Some(coefficient) = right[0].coefficient.replace(1.0) {
-                    //     right[0].coefficient = Some(-coefficient)
-                    // }
+                    // Subtracting a sum flips the sign of every one of its terms.
+                    for term in right.iter_mut() {
+                        term.is_sign_negative = !term.is_sign_negative;
+                    }
+                }
+                polynomial.append(&mut right);
+            }
+            // Products, quotients and powers of sums are expanded; those of single terms keep
+            // their writing.
+            NodeObject::Operator(Operator('*')) => {
+                let (left, right) = operands()?;
+                if left.len() > 1 || right.len() > 1 {
+                    return left.product(&right);
                 }
-                polynomial.append(&mut Polynomial::from(right));
+                polynomial.push(Term::try_from(branch.clone())?);
             }
-            _ => polynomial.push(Term::from(branch.clone())),
+            NodeObject::Operator(Operator('/')) => {
+                let (left, divisor) = operands()?;
+                if divisor.iter().all(|term| term.coefficient().is_zero()) {
+                    return Err(anyhow!("DIVISION_BY_ZERO_ERR"));
+                }
+                match (left.len(), divisor.first().and_then(Term::reciprocal)) {
+                    (2.., Some(reciprocal)) if divisor.len() == 1 => {
+                        return left.product(&Polynomial::from(vec![reciprocal]));
+                    }
+                    // Dividing by a sum must be exact, see `RationalFunction` otherwise.
+                    _ if divisor.len() > 1 => {
+                        let (quotient, remainder) = left.div_rem(&divisor)?;
+                        if !remainder.is_empty() {
+                            return Err(anyhow!(
+                                "{}{}",
+                                "INEXACT_DIVISION_ERR",
+                                remainder.to_string().trim_end()
+                            ));
+                        }
+                        return Ok(quotient);
+                    }
+                    _ => polynomial.push(Term::try_from(branch.clone())?),
+                }
+            }
+            NodeObject::Operator(Operator('%')) => {
+                let (left, divisor) = operands()?;
+                return Ok(left.div_rem(&divisor)?.1);
+            }
+            NodeObject::Operator(Operator('^')) => {
+                let base = Polynomial::try_from(node.left.clone().context("UNEXP_END_ERR")?)?;
+                if base.len() == 1 {
+                    polynomial.push(Term::try_from(branch.clone())?);
+                } else {
+                    let right = node
+                        .right
+                        .clone()
+                        .context("UNEXP_END_ERR")?
+                        .borrow()
+                        .clone();
+                    // Only natural powers of a sum expand into a polynomial.
+                    match right.object.into() {
+                        Number(exponent)
+                            if exponent >= 0.0
+                                && exponent.fract() == 0.0
+                                && exponent <= u32::MAX as f64 =>
+                        {
+                            return base.power(exponent as u32);
+                        }
+                        Number(exponent) => {
+                            return Err(anyhow!("{}{}", "SUM_EXPONENT_ERR", exponent))
+                        }
+                        token => return Err(anyhow!("{}{:?}", "UNEXP_TOKEN_ERR", token)),
+                    }
+                }
+            }
+            _ => polynomial.push(Term::try_from(branch.clone())?),
         }
-        polynomial
+        Ok(polynomial)
     }
 }
 
 impl Display for Polynomial {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut terms = self.iter();
-        if let Some(first) = terms.next() {
+        if self.is_empty() {
+            write!(f, "0 * X^0 ")?;
+        } else if let Some(first) = terms.next() {
             if first.is_sign_negative {
                 write!(f, "- ")?;
             }
@@ -238,3 +1309,371 @@ impl Display for Polynomial {
         Ok(())
     }
 }
+
+impl Polynomial {
+    /// Sum with like terms combined. Fails when a coefficient overflows.
+    pub fn checked_add(mut self, mut rhs: Self) -> Result<Self> {
+        self.append(&mut rhs);
+        self.reduce()?;
+        Ok(self)
+    }
+
+    /// Difference with like terms combined. Fails when a coefficient overflows.
+    pub fn checked_sub(self, rhs: Self) -> Result<Self> {
+        self.checked_add(-rhs)
+    }
+
+    /// Expanded product with like terms combined. Fails when a coefficient overflows.
+    pub fn checked_mul(self, rhs: Self) -> Result<Self> {
+        let mut product = self.product(&rhs)?;
+        product.reduce()?;
+        Ok(product)
+    }
+
+    /// Every coefficient multiplied by `rhs`. Fails when one overflows.
+    pub fn checked_scale(mut self, rhs: Rational) -> Result<Self> {
+        for term in self.iter_mut() {
+            let coefficient = term
+                .coefficient()
+                .checked_mul(rhs)
+                .context("RATIONAL_OVERFLOW_ERR")?;
+            term.is_sign_negative = coefficient.is_negative();
+            term.coefficient = Some(coefficient.abs());
+        }
+        self.reduce()?;
+        Ok(self)
+    }
+
+    /// Quotient of the Euclidean division, see `Polynomial::div_rem`.
+    pub fn checked_div(self, rhs: Self) -> Result<Self> {
+        Ok(self.div_rem(&rhs)?.0)
+    }
+
+    /// Remainder of the Euclidean division, see `Polynomial::div_rem`.
+    pub fn checked_rem(self, rhs: Self) -> Result<Self> {
+        Ok(self.div_rem(&rhs)?.1)
+    }
+}
+
+impl Add for Polynomial {
+    type Output = Self;
+
+    /// See `Polynomial::checked_add`.
+    ///
+    /// # Panics
+    ///
+    /// When a coefficient overflows.
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("RATIONAL_OVERFLOW_ERR")
+    }
+}
+
+impl Sub for Polynomial {
+    type Output = Self;
+
+    /// See `Polynomial::checked_sub`.
+    ///
+    /// # Panics
+    ///
+    /// When a coefficient overflows.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect("RATIONAL_OVERFLOW_ERR")
+    }
+}
+
+impl Neg for Polynomial {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for term in self.iter_mut() {
+            term.is_sign_negative = !term.is_sign_negative;
+        }
+        self
+    }
+}
+
+impl Mul for Polynomial {
+    type Output = Self;
+
+    /// See `Polynomial::checked_mul`.
+    ///
+    /// # Panics
+    ///
+    /// When a coefficient overflows.
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect("RATIONAL_OVERFLOW_ERR")
+    }
+}
+
+impl Mul<Rational> for Polynomial {
+    type Output = Self;
+
+    /// See `Polynomial::checked_scale`.
+    ///
+    /// # Panics
+    ///
+    /// When a coefficient overflows.
+    fn mul(self, rhs: Rational) -> Self::Output {
+        self.checked_scale(rhs).expect("RATIONAL_OVERFLOW_ERR")
+    }
+}
+
+impl Div for Polynomial {
+    type Output = Self;
+
+    /// See `Polynomial::checked_div`.
+    ///
+    /// # Panics
+    ///
+    /// When the divisor is zero, the unknowns differ or a coefficient overflows.
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).expect("POLYNOMIAL_DIVISION_ERR")
+    }
+}
+
+impl Rem for Polynomial {
+    type Output = Self;
+
+    /// See `Polynomial::checked_rem`.
+    ///
+    /// # Panics
+    ///
+    /// When the divisor is zero, the unknowns differ or a coefficient overflows.
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.checked_rem(rhs).expect("POLYNOMIAL_DIVISION_ERR")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rationals(integers: &[i128]) -> Vec<Rational> {
+        integers.iter().copied().map(Rational::from).collect()
+    }
+
+    fn x(coefficients: &[i128]) -> Polynomial {
+        Polynomial::from_coefficients(&rationals(coefficients), "X")
+    }
+
+    fn parse(input: &str) -> Polynomial {
+        Polynomial::try_from(crate::parser::Parser::new().parse(input).unwrap().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_gcd() {
+        // (X - 1)(X + 2) and (X - 1)(X - 3)
+        let divisor = gcd(&rationals(&[-2, 1, 1]), &rationals(&[3, -4, 1]));
+        assert_eq!(Some(rationals(&[-1, 1])), divisor);
+        assert_eq!(
+            Some(rationals(&[1])),
+            gcd(&rationals(&[1, 1]), &rationals(&[-1, 1]))
+        );
+    }
+
+    #[test]
+    fn test_square_free() {
+        // (X - 2)^3 (X + 1)^2 X
+        let coefficients = rationals(&[0, -8, -4, 10, 1, -4, 1]);
+        assert_eq!(
+            Some(vec![
+                (rationals(&[0, 1]), 1),
+                (rationals(&[1, 1]), 2),
+                (rationals(&[-2, 1]), 3)
+            ]),
+            square_free(&coefficients)
+        );
+
+        let polynomial = Polynomial::from_coefficients(&rationals(&[1, 2, 1]), "Y");
+        let factors = polynomial.square_free().unwrap();
+        assert_eq!(1, factors.len());
+        assert_eq!("1 * Y^0 + 1 * Y^1", factors[0].0.to_string().trim_end());
+        assert_eq!(2, factors[0].1);
+        assert_eq!(
+            "2 * Y^0 + 2 * Y^1",
+            polynomial.derivative(1).unwrap().to_string().trim_end()
+        );
+        assert_eq!(
+            "2 * Y^0",
+            polynomial.derivative(2).unwrap().to_string().trim_end()
+        );
+        assert!(polynomial.derivative(3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_clear_denominators() {
+        // X^-2 + 3 / X - 1 = 0 becomes 1 + 3X - X^2 = 0.
+        let mut polynomial = parse("X^-2 + 3 / X = 1");
+        assert_eq!(
+            vec![("X".to_owned(), 2.0)],
+            polynomial.clear_denominators(false)
+        );
+        assert_eq!(rationals(&[1, 3, -1]), polynomial.coefficients().unwrap());
+
+        // An odd power is raised to an even one, keeping the sign for inequalities.
+        let mut polynomial = parse("X^-1 = 2");
+        assert_eq!(
+            vec![("X".to_owned(), 2.0)],
+            polynomial.clear_denominators(true)
+        );
+        assert_eq!(rationals(&[0, 1, -2]), polynomial.coefficients().unwrap());
+
+        assert!(parse("X^2 = 1").clear_denominators(false).is_empty());
+    }
+
+    #[test]
+    fn test_substitute_root() {
+        let mut polynomial = parse("X - 3 * X^0.5 + X^(1/3) = 0");
+        assert!(polynomial.degree().is_err());
+        assert!(polynomial.solve(&Strategy::ClosedForm).is_err());
+        assert_eq!(6, polynomial.substitute_root("X", "Y").unwrap());
+        assert_eq!(vec!["Y".to_owned()], polynomial.identifiers());
+        assert_eq!(
+            rationals(&[0, 0, 1, -3, 0, 0, 1]),
+            polynomial.coefficients().unwrap()
+        );
+
+        let mut polynomial = parse("X^2 = 1");
+        assert_eq!(1, polynomial.substitute_root("X", "Y").unwrap());
+        assert_eq!(vec!["X".to_owned()], polynomial.identifiers());
+    }
+
+    #[test]
+    fn test_sparse() {
+        let mut polynomial = parse("X^1000000 = 1");
+        assert_eq!(1_000_000, polynomial.degree().unwrap());
+        assert_eq!(
+            Complex::default(),
+            polynomial.evaluate(Complex::from(-1.0)).unwrap()
+        );
+        assert!(polynomial.coefficients().is_err());
+
+        // Changing the terms drops the coefficients converted from them.
+        polynomial.retain(|term| term.identifier.is_none());
+        assert_eq!(0, polynomial.degree().unwrap());
+        assert_eq!(rationals(&[-1]), polynomial.coefficients().unwrap());
+    }
+
+    #[test]
+    fn test_expand() {
+        let expand = |input: &str| {
+            let mut polynomial = parse(input);
+            polynomial.reduce().unwrap();
+            polynomial.coefficients().unwrap()
+        };
+        assert_eq!(rationals(&[-1, 0, 1]), expand("(X + 1) * (X - 1) = 0"));
+        assert_eq!(rationals(&[-5, 4, 1]), expand("(X + 2)^2 = 9"));
+        assert_eq!(
+            rationals(&[0, -2, -1, 1]),
+            expand("X * (X + 1) * (X - 2) = 0")
+        );
+        assert_eq!(rationals(&[1, 3, 3, 1]), expand("2 * (X + 1)^3 / 2 = 0"));
+        assert_eq!(
+            rationals(&[1, 6, 15, 20, 15, 6, 1]),
+            expand("(X + 1)^6 = 0")
+        );
+        assert_eq!(rationals(&[-1, 0, 0, 8]), expand("(2 * X)^3 = 1"));
+        assert_eq!(rationals(&[0, 0, 0, 0, 0, 0, 1]), expand("(X^2)^3 = 0"));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // (X + 1) and (X - 1)
+        let (a, b) = (x(&[1, 1]), x(&[-1, 1]));
+        assert_eq!(
+            rationals(&[0, 2]),
+            (a.clone() + b.clone()).coefficients().unwrap()
+        );
+        assert_eq!(
+            rationals(&[2]),
+            (a.clone() - b.clone()).coefficients().unwrap()
+        );
+        assert_eq!(rationals(&[-1, -1]), (-a.clone()).coefficients().unwrap());
+        assert_eq!(
+            rationals(&[-1, 0, 1]),
+            (a.clone() * b.clone()).coefficients().unwrap()
+        );
+        assert_eq!(
+            rationals(&[3, 3]),
+            (a.clone() * Rational::from(3)).coefficients().unwrap()
+        );
+
+        // X^3 + 2 = (X^2 - X + 1)(X + 1) + 1
+        let c = x(&[2, 0, 0, 1]);
+        assert_eq!(
+            rationals(&[1, -1, 1]),
+            (c.clone() / a.clone()).coefficients().unwrap()
+        );
+        assert_eq!(rationals(&[1]), (c.clone() % a).coefficients().unwrap());
+        assert!(c.div_rem(&Polynomial::default()).is_err());
+        let y = Polynomial::from_coefficients(&rationals(&[1, 1]), "Y");
+        assert!(c.clone().checked_div(y.clone()).is_err());
+        assert!(c.clone().checked_rem(y).is_err());
+        let large = x(&[i128::MAX, 1]);
+        assert!(large.clone().checked_add(large.clone()).is_err());
+        assert!(large.checked_scale(Rational::from(2)).is_err());
+        assert_eq!(Complex::from(10.0), c.evaluate(Complex::from(2.0)).unwrap());
+        assert_eq!(
+            Complex::new(2.0, -1.0),
+            c.evaluate(Complex::new(0.0, 1.0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_gcd_variants() {
+        // (X - 1)^2 (X + 2) and (X - 1)(X + 3)(X - 4)
+        let (a, b) = (x(&[2, -3, 0, 1]), x(&[12, -11, -2, 1]));
+        assert_eq!(
+            rationals(&[-1, 1]),
+            a.gcd(&b).unwrap().coefficients().unwrap()
+        );
+        assert_eq!(
+            rationals(&[-1, 1]),
+            a.subresultant_gcd(&b).unwrap().coefficients().unwrap()
+        );
+        assert_eq!(
+            rationals(&[1]),
+            x(&[1, 1])
+                .subresultant_gcd(&x(&[-1, 1]))
+                .unwrap()
+                .coefficients()
+                .unwrap()
+        );
+        let approximate = a.approximate_gcd(&b, 1e-9).unwrap();
+        assert_eq!(2, approximate.len());
+        assert!((approximate[0] + 1.0).abs() < 1e-9);
+
+        // (X - 1)^2 (X + 2)(X + 3)(X - 4)
+        assert_eq!(
+            rationals(&[-24, 34, 5, -15, -1, 1]),
+            a.lcm(&b).unwrap().coefficients().unwrap()
+        );
+        assert_eq!(
+            rationals(&[0]),
+            a.lcm(&Polynomial::default())
+                .unwrap()
+                .coefficients()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_integral() {
+        // X^2 + 2X + 1 integrates to 3 + X + X^2 + X^3 / 3.
+        let polynomial = Polynomial::from_coefficients(&rationals(&[1, 2, 1]), "X");
+        let integral = polynomial.integral(Rational::from(3)).unwrap();
+        assert_eq!(
+            vec![
+                Rational::from(3),
+                Rational::ONE,
+                Rational::ONE,
+                Rational::new(1, 3)
+            ],
+            integral.coefficients().unwrap()
+        );
+        assert_eq!(
+            polynomial.coefficients().unwrap(),
+            integral.derivative(1).unwrap().coefficients().unwrap()
+        );
+    }
+}
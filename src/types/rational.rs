@@ -1,23 +1,358 @@
-use derive_more::Display;
-use super::{Type, polynomial::Term};
+use super::{polynomial::Term, Type};
+use anyhow::{anyhow, Error, Result};
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt::{Display, Formatter},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
 
-#[derive(Debug, Display, Clone)]
-pub struct Rational(pub f64);
+/// Error of every exact computation whose numerator or denominator overflows.
+pub(crate) const OVERFLOW_ERR: &str = "RATIONAL_OVERFLOW_ERR";
+
+/// Exact fraction kept in lowest terms, with the sign carried by the numerator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+pub fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i128
+}
+
+/// Least common multiple, or `None` on overflow.
+pub fn lcm(a: i128, b: i128) -> Option<i128> {
+    match gcd(a, b) {
+        0 => Some(0),
+        divisor => (a / divisor).checked_mul(b).map(i128::abs),
+    }
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Rational = Rational {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    /// Panics when the denominator is zero.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        Rational::checked_new(numerator, denominator).expect("RATIONAL_ZERO_DENOMINATOR_ERR")
+    }
+
+    pub fn checked_new(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator).max(1);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+        if denominator < 0 {
+            Some(Rational {
+                numerator: numerator.checked_neg()?,
+                denominator: denominator.checked_neg()?,
+            })
+        } else {
+            Some(Rational {
+                numerator,
+                denominator,
+            })
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numerator < 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn abs(&self) -> Self {
+        Rational {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator,
+        }
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        // Scaling by the lcm of the denominators rather than their product delays overflow.
+        let divisor = gcd(self.denominator, rhs.denominator);
+        let numerator = self
+            .numerator
+            .checked_mul(rhs.denominator / divisor)?
+            .checked_add(rhs.numerator.checked_mul(self.denominator / divisor)?)?;
+        Rational::checked_new(
+            numerator,
+            self.denominator.checked_mul(rhs.denominator / divisor)?,
+        )
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // Cancelling crosswise first keeps the intermediate products small.
+        let a = gcd(self.numerator, rhs.denominator).max(1);
+        let b = gcd(rhs.numerator, self.denominator).max(1);
+        Rational::checked_new(
+            (self.numerator / a).checked_mul(rhs.numerator / b)?,
+            (self.denominator / b).checked_mul(rhs.denominator / a)?,
+        )
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(rhs.checked_recip()?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Rational {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+
+    pub fn checked_recip(self) -> Option<Self> {
+        Rational::checked_new(self.denominator, self.numerator)
+    }
+
+    pub fn checked_pow(self, exponent: u32) -> Option<Self> {
+        Some(Rational {
+            numerator: self.numerator.checked_pow(exponent)?,
+            denominator: self.denominator.checked_pow(exponent)?,
+        })
+    }
+
+    pub fn recip(self) -> Self {
+        self.checked_recip().expect("RATIONAL_ZERO_DENOMINATOR_ERR")
+    }
+
+    pub fn pow(self, exponent: u32) -> Self {
+        self.checked_pow(exponent).expect(OVERFLOW_ERR)
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::ZERO
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(numerator: i128) -> Self {
+        Rational {
+            numerator,
+            denominator: 1,
+        }
+    }
+}
+
+impl From<Rational> for f64 {
+    fn from(rational: Rational) -> Self {
+        rational.numerator as f64 / rational.denominator as f64
+    }
+}
+
+impl TryFrom<f64> for Rational {
+    type Error = Error;
+
+    /// Converts through the shortest decimal representation of the float, so that `9.3`
+    /// becomes `93/10` rather than the exact binary value closest to it.
+    fn try_from(value: f64) -> Result<Self> {
+        if !value.is_finite() {
+            return Err(anyhow!("{}{}", "RATIONAL_CONVERSION_ERR", value));
+        }
+        let decimal = value.abs().to_string();
+        let (integer, fraction) = decimal.split_once('.').unwrap_or((&decimal, ""));
+        let denominator = 10_i128
+            .checked_pow(fraction.len() as u32)
+            .ok_or_else(|| anyhow!(OVERFLOW_ERR))?;
+        let numerator: i128 = format!("{integer}{fraction}")
+            .parse()
+            .map_err(|_| anyhow!(OVERFLOW_ERR))?;
+        let rational = Rational::new(numerator, denominator);
+        Ok(if value.is_sign_negative() { -rational } else { rational })
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect(OVERFLOW_ERR)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect(OVERFLOW_ERR)
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect(OVERFLOW_ERR)
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).expect("RATIONAL_DIVISION_ERR")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect(OVERFLOW_ERR)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (
+            self.numerator.checked_mul(other.denominator),
+            other.numerator.checked_mul(self.denominator),
+        ) {
+            (Some(lhs), Some(rhs)) => lhs.cmp(&rhs),
+            _ => f64::from(*self).total_cmp(&f64::from(*other)),
+        }
+    }
+}
+
+impl Display for Rational {
+    /// Integers and terminating decimals are printed in decimal notation, any other value as
+    /// an irreducible fraction. The alternate flag (`{:#}`) prints every non-integer as a
+    /// fraction.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() && !self.is_integer() {
+            return write!(f, "{}/{}", self.numerator, self.denominator);
+        }
+        let (mut twos, mut fives, mut rest) = (0, 0, self.denominator);
+        while rest % 2 == 0 {
+            rest /= 2;
+            twos += 1;
+        }
+        while rest % 5 == 0 {
+            rest /= 5;
+            fives += 1;
+        }
+        let places = twos.max(fives);
+        let scaled = 10_i128
+            .checked_pow(places)
+            .and_then(|power| self.numerator.checked_mul(power / self.denominator));
+
+        match scaled {
+            Some(scaled) if rest == 1 && places > 0 => {
+                let digits = format!("{:0>width$}", scaled.unsigned_abs(), width = places as usize + 1);
+                let (integer, fraction) = digits.split_at(digits.len() - places as usize);
+                let sign = if self.is_negative() { "-" } else { "" };
+                write!(f, "{sign}{integer}.{fraction}")
+            }
+            _ if self.is_integer() => write!(f, "{}", self.numerator),
+            _ => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
 
 impl Type for Rational {
     fn node_color<'a>(&self) -> &'a str {
         "#00A0B0"
     }
 
-    fn into_term(&self) -> Term {
-        let mut term = Term::default();
-
-        if self.0.is_sign_negative() {
-            term.is_sign_negative = true;
-            term.coefficient = Some(-self.0);
-        } else {
-            term.coefficient = Some(self.0);
+    fn to_term(&self) -> Term {
+        Term {
+            is_sign_negative: self.is_negative(),
+            coefficient: Some(self.abs()),
+            ..Default::default()
         }
-        term
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalization() {
+        assert_eq!(Rational::new(1, 2), Rational::new(-3, -6));
+        assert_eq!(Rational::new(-1, 2), Rational::new(3, -6));
+        assert_eq!(Rational::ZERO, Rational::new(0, -5));
+        assert_eq!(None, Rational::checked_new(1, 0));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let third = Rational::new(1, 3);
+        let half = Rational::new(1, 2);
+        assert_eq!(Rational::new(5, 6), third + half);
+        assert_eq!(Rational::new(-1, 6), third - half);
+        assert_eq!(Rational::new(1, 6), third * half);
+        assert_eq!(Rational::new(2, 3), third / half);
+        assert_eq!(Rational::new(1, 27), third.pow(3));
+        assert!(third < half);
+        assert!(-half < third);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let large = Rational::from(i128::MAX);
+        assert_eq!(None, large.checked_add(Rational::ONE));
+        assert_eq!(None, large.checked_mul(Rational::from(2)));
+        assert_eq!(Some(Rational::ONE), large.checked_div(large));
+        assert!(Rational::try_from(1e300).is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Rational::new(93, 10), Rational::try_from(9.3).unwrap());
+        assert_eq!(Rational::new(-1, 4), Rational::try_from(-0.25).unwrap());
+        assert_eq!(Rational::from(42), Rational::try_from(42.0).unwrap());
+        assert_eq!(0.75, f64::from(Rational::new(3, 4)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("9.3", Rational::new(93, 10).to_string());
+        assert_eq!("-0.25", Rational::new(-1, 4).to_string());
+        assert_eq!("-0.05", Rational::new(-1, 20).to_string());
+        assert_eq!("1/3", Rational::new(1, 3).to_string());
+        assert_eq!("-7/6", Rational::new(-7, 6).to_string());
+        assert_eq!("42", Rational::from(42).to_string());
+        assert_eq!("-1/4", format!("{:#}", Rational::new(-1, 4)));
+        assert_eq!("42", format!("{:#}", Rational::from(42)));
+    }
+}
@@ -0,0 +1,283 @@
+use super::polynomial::{divide, gcd, multiply, subtract, trim, Polynomial, MAX_DENSE_DEGREE};
+use super::rational::{Rational, OVERFLOW_ERR};
+use crate::node::{Branch, NodeObject};
+use crate::tokenizer::Token::*;
+use anyhow::{anyhow, Context, Error, Result};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// Quotient of two polynomials in one identifier, in lowest terms and with a monic
+/// denominator, so that an exact quotient has the denominator 1. Coefficients are indexed by
+/// exponent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RationalFunction {
+    identifier: Option<String>,
+    numerator: Vec<Rational>,
+    denominator: Vec<Rational>,
+}
+
+impl RationalFunction {
+    /// Cancels the greatest common divisor of both polynomials. `None` when the denominator is
+    /// zero or a coefficient overflows.
+    pub fn new(
+        numerator: &[Rational],
+        denominator: &[Rational],
+        identifier: Option<String>,
+    ) -> Option<Self> {
+        let denominator = trim(denominator.to_vec());
+        let divisor = gcd(numerator, &denominator)?;
+        let numerator = divide(numerator, &divisor)?.0;
+        let denominator = divide(&denominator, &divisor)?.0;
+        let leading = *denominator.last()?;
+        let monic = |coefficients: Vec<Rational>| {
+            coefficients
+                .into_iter()
+                .map(|c| c.checked_div(leading))
+                .collect::<Option<Vec<_>>>()
+        };
+        Some(RationalFunction {
+            identifier,
+            numerator: monic(numerator)?,
+            denominator: monic(denominator)?,
+        })
+    }
+
+    fn polynomial(coefficients: &[Rational], identifier: Option<String>) -> Self {
+        RationalFunction {
+            identifier,
+            numerator: trim(coefficients.to_vec()),
+            denominator: vec![Rational::ONE],
+        }
+    }
+
+    /// Identifier shared by both operands, failing when they are in different ones.
+    fn shared_identifier(&self, other: &Self) -> Result<Option<String>> {
+        match (&self.identifier, &other.identifier) {
+            (Some(a), Some(b)) if a != b => {
+                Err(anyhow!("{}{}, {}", "MULTIVARIATE_DIVISION_ERR", a, b))
+            }
+            (a, b) => Ok(a.clone().or_else(|| b.clone())),
+        }
+    }
+
+    /// Name of the unknown, `X` when the function is a constant.
+    pub fn identifier(&self) -> &str {
+        self.identifier.as_deref().unwrap_or("X")
+    }
+
+    pub fn is_polynomial(&self) -> bool {
+        self.denominator.len() == 1
+    }
+
+    pub fn numerator(&self) -> Polynomial {
+        Polynomial::from_coefficients(&self.numerator, self.identifier())
+    }
+
+    pub fn denominator(&self) -> Polynomial {
+        Polynomial::from_coefficients(&self.denominator, self.identifier())
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Result<Self> {
+        let identifier = self.shared_identifier(other)?;
+        let numerator = multiply(&self.numerator, &other.denominator)
+            .zip(multiply(&other.numerator, &self.denominator))
+            .and_then(|(a, b)| subtract(&a, &b.into_iter().map(|c| -c).collect::<Vec<_>>()))
+            .context(OVERFLOW_ERR)?;
+        let denominator = multiply(&self.denominator, &other.denominator).context(OVERFLOW_ERR)?;
+        RationalFunction::new(&numerator, &denominator, identifier).context(OVERFLOW_ERR)
+    }
+
+    pub fn checked_neg(&self) -> Result<Self> {
+        Ok(RationalFunction {
+            numerator: self
+                .numerator
+                .iter()
+                .map(|c| c.checked_neg())
+                .collect::<Option<_>>()
+                .context(OVERFLOW_ERR)?,
+            ..self.clone()
+        })
+    }
+
+    pub fn checked_mul(&self, other: &Self) -> Result<Self> {
+        let identifier = self.shared_identifier(other)?;
+        let numerator = multiply(&self.numerator, &other.numerator).context(OVERFLOW_ERR)?;
+        let denominator = multiply(&self.denominator, &other.denominator).context(OVERFLOW_ERR)?;
+        RationalFunction::new(&numerator, &denominator, identifier).context(OVERFLOW_ERR)
+    }
+
+    pub fn checked_div(&self, other: &Self) -> Result<Self> {
+        if other.numerator.is_empty() {
+            return Err(anyhow!("DIVISION_BY_ZERO_ERR"));
+        }
+        let identifier = self.shared_identifier(other)?;
+        let numerator = multiply(&self.numerator, &other.denominator).context(OVERFLOW_ERR)?;
+        let denominator = multiply(&self.denominator, &other.numerator).context(OVERFLOW_ERR)?;
+        RationalFunction::new(&numerator, &denominator, identifier).context(OVERFLOW_ERR)
+    }
+
+    /// Remainder of the Euclidean division, which only polynomials have.
+    pub fn checked_rem(&self, other: &Self) -> Result<Self> {
+        if !self.is_polynomial() || !other.is_polynomial() {
+            return Err(anyhow!("{}{} % {}", "REMAINDER_ERR", self, other));
+        }
+        if other.numerator.is_empty() {
+            return Err(anyhow!("DIVISION_BY_ZERO_ERR"));
+        }
+        let identifier = self.shared_identifier(other)?;
+        let (_, remainder) = divide(&self.numerator, &other.numerator).context(OVERFLOW_ERR)?;
+        Ok(RationalFunction::polynomial(&remainder, identifier))
+    }
+
+    /// Integer power by repeated squaring, a negative one inverting the function. Fails when
+    /// the power has a degree beyond `MAX_DENSE_DEGREE`.
+    pub fn checked_pow(&self, exponent: i32) -> Result<Self> {
+        let degree = (self.numerator.len().max(self.denominator.len()) - 1) as u64
+            * u64::from(exponent.unsigned_abs());
+        if degree > MAX_DENSE_DEGREE as u64 {
+            return Err(anyhow!("{}{}", "DEGREE_LIMIT_ERR", degree));
+        }
+        let mut power = RationalFunction::polynomial(&[Rational::ONE], self.identifier.clone());
+        let mut base = self.clone();
+        let mut n = exponent.unsigned_abs();
+        while n > 0 {
+            if n % 2 == 1 {
+                power = power.checked_mul(&base)?;
+            }
+            n /= 2;
+            if n > 0 {
+                base = base.checked_mul(&base)?;
+            }
+        }
+        match exponent {
+            0.. => Ok(power),
+            _ => RationalFunction::polynomial(&[Rational::ONE], None).checked_div(&power),
+        }
+    }
+}
+
+impl TryFrom<Branch> for RationalFunction {
+    type Error = Error;
+
+    /// Evaluates sums, products, quotients, remainders and integer powers exactly. Any other
+    /// node is a term, whose negative powers become a denominator.
+    fn try_from(branch: Branch) -> Result<Self> {
+        let node = branch.borrow().clone();
+        let operands = || -> Result<(Self, Self)> {
+            Ok((
+                RationalFunction::try_from(node.left.clone().context("UNEXP_END_ERR")?)?,
+                RationalFunction::try_from(node.right.clone().context("UNEXP_END_ERR")?)?,
+            ))
+        };
+        match &node.object {
+            NodeObject::Operator(Operator(operator @ ('+' | '-' | '*' | '/' | '%'))) => {
+                let (left, right) = operands()?;
+                match operator {
+                    '+' => left.checked_add(&right),
+                    '-' => left.checked_add(&right.checked_neg()?),
+                    '*' => left.checked_mul(&right),
+                    '/' => left.checked_div(&right),
+                    _ => left.checked_rem(&right),
+                }
+            }
+            NodeObject::Operator(Operator('^')) => {
+                let base = RationalFunction::try_from(node.left.clone().context("UNEXP_END_ERR")?)?;
+                let exponent = node.right.clone().context("UNEXP_END_ERR")?;
+                let exponent = exponent.borrow().object.clone();
+                match exponent.into() {
+                    Number(exponent) if exponent.fract() == 0.0 => {
+                        base.checked_pow(exponent as i32)
+                    }
+                    Number(exponent) => Err(anyhow!("{}{}", "NON_INTEGER_EXPONENT_ERR", exponent)),
+                    token => Err(anyhow!("{}{:?}", "UNEXP_TOKEN_ERR", token)),
+                }
+            }
+            _ => {
+                let mut polynomial = Polynomial::try_from(branch.clone())?;
+                let identifiers = polynomial.identifiers();
+                if identifiers.len() > 1 {
+                    return Err(anyhow!(
+                        "{}{}",
+                        "MULTIVARIATE_DIVISION_ERR",
+                        identifiers.join(", ")
+                    ));
+                }
+                let identifier = identifiers.into_iter().next();
+                let cleared = polynomial.clear_denominators(false);
+                let mut denominator =
+                    vec![Rational::ZERO; cleared.first().map_or(0, |c| c.1 as usize)];
+                denominator.push(Rational::ONE);
+                RationalFunction::new(&polynomial.coefficients()?, &denominator, identifier)
+                    .context(OVERFLOW_ERR)
+            }
+        }
+    }
+}
+
+impl Display for RationalFunction {
+    /// Writes the numerator alone when the denominator is 1, otherwise both within
+    /// parentheses, e.g. `(1 * X^0) / (1 * X^0 + 1 * X^1)`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let numerator = self.numerator().to_string();
+        if self.is_polynomial() {
+            return write!(f, "{}", numerator.trim_end());
+        }
+        write!(
+            f,
+            "({}) / ({})",
+            numerator.trim_end(),
+            self.denominator().to_string().trim_end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn parse(input: &str) -> Result<RationalFunction> {
+        RationalFunction::try_from(Parser::new().parse(input).unwrap().unwrap())
+    }
+
+    fn rationals(integers: &[i128]) -> Vec<Rational> {
+        integers.iter().copied().map(Rational::from).collect()
+    }
+
+    #[test]
+    fn test_exact_division() {
+        let function = parse("(X^2 - 1) / (X - 1) = 0").unwrap();
+        assert!(function.is_polynomial());
+        assert_eq!(rationals(&[1, 1]), function.numerator);
+        let function = parse("(X^3 + 2) % (X + 1) = 0").unwrap();
+        assert_eq!(rationals(&[1]), function.numerator);
+    }
+
+    #[test]
+    fn test_lowest_terms() {
+        // X / (X^2 - X) - 1 = (2 - X) / (X - 1)
+        let function = parse("X / (X^2 - X) = 1").unwrap();
+        assert_eq!(rationals(&[2, -1]), function.numerator);
+        assert_eq!(rationals(&[-1, 1]), function.denominator);
+        assert_eq!(
+            "(2 * X^0 - 1 * X^1) / (- 1 * X^0 + 1 * X^1)",
+            function.to_string()
+        );
+
+        // 1 / X^2 + 1 / (2 * X + 2) has the common denominator X^3 + X^2.
+        let function = parse("X^-2 + 1 / (2 * X + 2) = 0").unwrap();
+        assert_eq!(rationals(&[0, 0, 1, 1]), function.denominator);
+        assert_eq!(
+            vec![Rational::ONE, Rational::ONE, Rational::new(1, 2)],
+            function.numerator
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse("1 / (X - X) = 0").is_err());
+        assert!(parse("(X + 1) % (1 / X) = 0").is_err());
+        assert!(parse("1 / (X + Y) = 0").is_err());
+        assert!(parse("1 / (X^1000000 - 1) = 0").is_err());
+    }
+}
@@ -0,0 +1,352 @@
+use super::polynomial::{derivative, divide, gcd, integer_coefficients, rational_roots, trim};
+use super::rational::{self, Rational};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// Sequence `p0, p1, ..., pk` with `p0` the square-free part of a polynomial, `p1` its
+/// derivative and each further term the negated remainder of the previous two. The number of
+/// distinct real roots in `(a, b]` is the drop in sign changes of the sequence from `a` to `b`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SturmSequence(Vec<Vec<Rational>>);
+
+/// Interval `(lower, upper]` holding exactly one real root, or that root itself when both
+/// bounds are equal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootInterval {
+    pub lower: Rational,
+    pub upper: Rational,
+}
+
+/// Positive multiple with coprime integer coefficients, which keeps the signs while stopping
+/// the growth of the fractions along the sequence.
+fn normalize(coefficients: &[Rational]) -> Option<Vec<Rational>> {
+    let integers = integer_coefficients(coefficients)?;
+    let divisor = integers
+        .iter()
+        .fold(0, |divisor, c| rational::gcd(divisor, *c))
+        .max(1);
+    Some(
+        integers
+            .into_iter()
+            .map(|c| Rational::from(c / divisor))
+            .collect(),
+    )
+}
+
+fn midpoint(lower: Rational, upper: Rational) -> Option<Rational> {
+    lower.checked_add(upper)?.checked_div(Rational::from(2))
+}
+
+/// Sign of a polynomial with integer coefficients at `x = p / q`, which is the sign of the
+/// integer `Σ ai p^i q^(n-i)` since `q > 0`. Unlike evaluating with fractions, no common
+/// denominator is formed, so far finer bisections fit. `None` when the sum overflows.
+fn sign(polynomial: &[Rational], x: Rational) -> Option<Ordering> {
+    let (p, q) = (x.numerator(), x.denominator());
+    let mut power = 1i128;
+    let mut value = 0i128;
+    for coefficient in polynomial.iter().rev() {
+        value = value
+            .checked_mul(p)?
+            .checked_add(coefficient.numerator().checked_mul(power)?)?;
+        power = power.checked_mul(q)?;
+    }
+    Some(value.cmp(&0))
+}
+
+impl SturmSequence {
+    /// `None` when a coefficient overflows. The zero polynomial gives an empty sequence.
+    pub fn new(coefficients: &[Rational]) -> Option<Self> {
+        let coefficients = trim(coefficients.to_vec());
+        if coefficients.is_empty() {
+            return Some(SturmSequence(vec![]));
+        }
+        let prime = derivative(&coefficients)?;
+        let part = divide(&coefficients, &gcd(&coefficients, &prime)?)?.0;
+        let mut sequence = vec![normalize(&part)?];
+        let mut next = normalize(&derivative(&part)?)?;
+        while !next.is_empty() {
+            let (_, remainder) = divide(&sequence[sequence.len() - 1], &next)?;
+            sequence.push(next);
+            next = normalize(
+                &remainder
+                    .into_iter()
+                    .map(Rational::checked_neg)
+                    .collect::<Option<Vec<_>>>()?,
+            )?;
+        }
+        Some(SturmSequence(sequence))
+    }
+
+    pub fn polynomials(&self) -> &[Vec<Rational>] {
+        &self.0
+    }
+
+    fn changes(signs: impl Iterator<Item = bool>) -> usize {
+        let mut signs = signs.peekable();
+        let mut changes = 0;
+        while let Some(sign) = signs.next() {
+            if signs.peek().is_some_and(|next| *next != sign) {
+                changes += 1;
+            }
+        }
+        changes
+    }
+
+    /// Sign changes of the sequence at `x`, skipping the terms that vanish there.
+    pub fn sign_changes(&self, x: Rational) -> Option<usize> {
+        let mut signs = vec![];
+        for polynomial in &self.0 {
+            match sign(polynomial, x)? {
+                Ordering::Equal => {}
+                ordering => signs.push(ordering == Ordering::Less),
+            }
+        }
+        Some(Self::changes(signs.into_iter()))
+    }
+
+    /// Number of distinct real roots in `(lower, upper]`.
+    pub fn count(&self, lower: Rational, upper: Rational) -> Option<usize> {
+        Some(
+            self.sign_changes(lower)?
+                .saturating_sub(self.sign_changes(upper)?),
+        )
+    }
+
+    /// Number of distinct real roots, from the signs of the leading coefficients at -∞ and
+    /// +∞.
+    pub fn count_all(&self) -> usize {
+        let at = |negative: bool| {
+            Self::changes(self.0.iter().map(|polynomial| {
+                let leading = polynomial[polynomial.len() - 1].is_negative();
+                leading ^ (negative && polynomial.len() % 2 == 0)
+            }))
+        };
+        at(true).saturating_sub(at(false))
+    }
+
+    /// Every real root lies in `(-B, B)` for Cauchy's bound `B = 1 + max |ai / an|`, rounded
+    /// up to an integer.
+    pub fn bound(&self) -> Option<Rational> {
+        let polynomial = match self.0.first() {
+            Some(polynomial) => polynomial,
+            None => return Some(Rational::ONE),
+        };
+        let leading = polynomial[polynomial.len() - 1];
+        let mut bound = Rational::ZERO;
+        for coefficient in &polynomial[..polynomial.len() - 1] {
+            bound = bound.max(coefficient.checked_div(leading)?.abs());
+        }
+        let ceiling = bound.numerator().checked_add(bound.denominator() - 1)? / bound.denominator();
+        Some(Rational::from(ceiling.checked_add(1)?))
+    }
+
+    /// Splits `(lower, upper]` by bisection into disjoint intervals holding one root each, in
+    /// increasing order. `None` when a value overflows.
+    pub fn isolate(&self, lower: Rational, upper: Rational) -> Option<Vec<RootInterval>> {
+        let mut intervals = vec![];
+        let mut pending = vec![(lower, upper, self.count(lower, upper)?)];
+        while let Some((lower, upper, count)) = pending.pop() {
+            match count {
+                0 => {}
+                1 => intervals.push(RootInterval { lower, upper }),
+                _ => {
+                    let middle = midpoint(lower, upper)?;
+                    let left = self.count(lower, middle)?;
+                    pending.push((middle, upper, count - left));
+                    pending.push((lower, middle, left));
+                }
+            }
+        }
+        Some(intervals)
+    }
+
+    /// Every real root, isolated within Cauchy's bound.
+    pub fn isolate_all(&self) -> Option<Vec<RootInterval>> {
+        let bound = self.bound()?;
+        self.isolate(bound.checked_neg()?, bound)
+    }
+
+    /// Halves an isolating interval until it is at most `width` wide, collapsing it onto the
+    /// root when a midpoint hits it exactly. Stops early, with the narrowest interval reached,
+    /// when the next halving overflows.
+    pub fn refine(&self, interval: RootInterval, width: Rational) -> RootInterval {
+        let RootInterval {
+            mut lower,
+            mut upper,
+        } = interval;
+        let vanishes =
+            |x: Rational| -> Option<bool> { Some(sign(self.0.first()?, x)? == Ordering::Equal) };
+        if lower != upper && vanishes(upper) == Some(true) {
+            lower = upper;
+        }
+        let mut halve = || -> Option<bool> {
+            if upper.checked_sub(lower)? <= width {
+                return Some(false);
+            }
+            let middle = midpoint(lower, upper)?;
+            if self.count(lower, middle)? == 1 {
+                if vanishes(middle)? {
+                    lower = middle;
+                }
+                upper = middle;
+            } else {
+                lower = middle;
+            }
+            Some(true)
+        };
+        while let Some(true) = halve() {}
+        RootInterval { lower, upper }
+    }
+
+    /// Roots in the closed interval `[lower, upper]`, each refined to `width`. Rational roots
+    /// are given exactly, since bisection rarely lands on them.
+    pub fn roots_in(
+        &self,
+        lower: Rational,
+        upper: Rational,
+        width: Rational,
+    ) -> Option<Vec<RootInterval>> {
+        let exact = self.0.first().map_or(vec![], |p| rational_roots(p));
+        let mut intervals = vec![];
+        if exact.contains(&lower) {
+            intervals.push(RootInterval {
+                lower,
+                upper: lower,
+            });
+        }
+        for interval in self.isolate(lower, upper)? {
+            match exact
+                .iter()
+                .find(|root| interval.lower < **root && **root <= interval.upper)
+            {
+                Some(root) => intervals.push(RootInterval {
+                    lower: *root,
+                    upper: *root,
+                }),
+                None => intervals.push(self.refine(interval, width)),
+            }
+        }
+        Some(intervals)
+    }
+}
+
+impl Display for RootInterval {
+    /// Writes the root itself when it is known exactly, otherwise the interval. Inexact bounds
+    /// are printed as decimals with the formatter precision, when one is given.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bound = |bound: Rational| match f.precision() {
+            Some(precision) if !bound.is_integer() => {
+                format!("{:.precision$}", f64::from(bound))
+            }
+            _ => bound.to_string(),
+        };
+        if self.lower == self.upper {
+            write!(f, "{}", self.lower)
+        } else {
+            write!(f, "({}, {}]", bound(self.lower), bound(self.upper))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(coefficients: &[i128]) -> SturmSequence {
+        let coefficients = coefficients
+            .iter()
+            .copied()
+            .map(Rational::from)
+            .collect::<Vec<_>>();
+        SturmSequence::new(&coefficients).unwrap()
+    }
+
+    #[test]
+    fn test_sequence() {
+        // X^3 - 3X + 1, 3X^2 - 3 and the negated remainder 2X - 1 scaled to coprime integers.
+        let sturm = sequence(&[1, -3, 0, 1]);
+        let polynomials = sturm
+            .polynomials()
+            .iter()
+            .map(|p| p.iter().map(|c| c.numerator()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![vec![1, -3, 0, 1], vec![-1, 0, 1], vec![-1, 2], vec![1]],
+            polynomials
+        );
+        assert_eq!(3, sturm.count_all());
+        assert_eq!(Some(2), sturm.count(Rational::ZERO, Rational::from(2)));
+    }
+
+    #[test]
+    fn test_repeated_roots() {
+        // (X - 1)^2 (X + 2) has two distinct roots.
+        let sturm = sequence(&[2, -3, 0, 1]);
+        assert_eq!(2, sturm.count_all());
+        assert_eq!(Some(1), sturm.count(Rational::ZERO, Rational::ONE));
+        assert_eq!(0, sequence(&[1, 0, 1]).count_all());
+    }
+
+    #[test]
+    fn test_isolate() {
+        let sturm = sequence(&[-2, 0, 1]);
+        let intervals = sturm.isolate_all().unwrap();
+        assert_eq!(2, intervals.len());
+        for interval in intervals {
+            assert_eq!(Some(1), sturm.count(interval.lower, interval.upper));
+            let refined = sturm.refine(interval, Rational::new(1, 1000));
+            assert!(refined.upper - refined.lower <= Rational::new(1, 1000));
+            let root = f64::from(refined.upper).abs();
+            assert!((root - 2f64.sqrt()).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_refine_high_degree() {
+        // X^6 - 3 overflows before reaching the width, and keeps the narrowest interval.
+        let sturm = sequence(&[-3, 0, 0, 0, 0, 0, 1]);
+        let width = Rational::new(1, 1_000_000);
+        let roots = sturm
+            .roots_in(Rational::from(-5), Rational::from(5), width)
+            .unwrap();
+        assert_eq!(2, roots.len());
+        for root in roots {
+            assert_eq!(Some(1), sturm.count(root.lower, root.upper));
+            assert!(root.upper - root.lower < Rational::new(1, 10_000));
+            let root = f64::from(root.upper).abs();
+            assert!((root - 3f64.powf(1. / 6.)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_roots_in() {
+        // X (X - 1)(X - 2) on [0, 1] includes both bounds.
+        let sturm = sequence(&[0, 2, -3, 1]);
+        let roots = sturm
+            .roots_in(Rational::ZERO, Rational::ONE, Rational::new(1, 100))
+            .unwrap();
+        assert_eq!(
+            vec![
+                RootInterval {
+                    lower: Rational::ZERO,
+                    upper: Rational::ZERO
+                },
+                RootInterval {
+                    lower: Rational::ONE,
+                    upper: Rational::ONE
+                }
+            ],
+            roots
+        );
+        assert_eq!(
+            "(1.41, 1.42]",
+            format!(
+                "{:.2}",
+                RootInterval {
+                    lower: Rational::new(141, 100),
+                    upper: Rational::new(142, 100)
+                }
+            )
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use super::{complex::Complex, rational::Rational};
+use std::fmt::{Display, Formatter};
+
+/// Largest prime tried when moving square factors out of a radical.
+const MAX_FACTOR: i128 = 1_000_000;
+
+/// Exact quadratic irrational `rational + coefficient * √radicand`. The radicand is square-free
+/// and negative for imaginary values, so denominators never contain a radical.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Surd {
+    pub rational: Rational,
+    pub coefficient: Rational,
+    pub radicand: i128,
+}
+
+/// Splits `n` into `outside² * inside`, moving every square factor out of the radical.
+fn extract_square(n: i128) -> (i128, i128) {
+    let (mut outside, mut inside) = (1, n);
+    let mut factor = 2;
+    while factor <= MAX_FACTOR && factor * factor <= inside.abs() {
+        while inside % (factor * factor) == 0 {
+            inside /= factor * factor;
+            outside *= factor;
+        }
+        factor += 1;
+    }
+    (outside, inside)
+}
+
+impl Surd {
+    /// Simplest form of `√value`, rationalizing the denominator as `√(n/d) = √(n·d)/d`.
+    pub fn sqrt(value: Rational) -> Option<Self> {
+        let radicand = value.numerator().checked_mul(value.denominator())?;
+        let (outside, inside) = extract_square(radicand);
+        Some(Surd {
+            rational: Rational::ZERO,
+            coefficient: Rational::new(outside, value.denominator()),
+            radicand: inside,
+        })
+    }
+
+    /// Roots of `a * x^2 + b * x + c` when they are irrational, i.e. when the discriminant is
+    /// not the square of a rational.
+    pub fn quadratic_roots(a: Rational, b: Rational, c: Rational) -> Option<[Surd; 2]> {
+        let discriminant = b
+            .checked_mul(b)?
+            .checked_sub(Rational::from(4).checked_mul(a)?.checked_mul(c)?)?;
+        let root = Surd::sqrt(discriminant)?;
+        if root.radicand == 1 || root.radicand == 0 {
+            return None;
+        }
+        let denominator = Rational::from(2).checked_mul(a)?;
+        let rational = b.checked_neg()?.checked_div(denominator)?;
+        let coefficient = root.coefficient.checked_div(denominator)?.abs();
+        Some([
+            Surd {
+                rational,
+                coefficient: -coefficient,
+                radicand: root.radicand,
+            },
+            Surd {
+                rational,
+                coefficient,
+                radicand: root.radicand,
+            },
+        ])
+    }
+
+    pub fn to_complex(&self) -> Complex {
+        let radical = f64::from(self.coefficient) * (self.radicand.abs() as f64).sqrt();
+        if self.radicand < 0 {
+            Complex::new(f64::from(self.rational), radical)
+        } else {
+            Complex::from(f64::from(self.rational) + radical)
+        }
+    }
+}
+
+impl Display for Surd {
+    /// Writes e.g. `-1/2 + 3√5/2`, `-√2` or `1 - i√3`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let magnitude = self.coefficient.numerator().abs();
+        let mut radical = String::new();
+        if magnitude != 1 {
+            radical.push_str(&magnitude.to_string());
+        }
+        if self.radicand < 0 {
+            radical.push('i');
+        }
+        if self.radicand.abs() != 1 {
+            radical.push_str(&format!("√{}", self.radicand.abs()));
+        }
+        if radical.is_empty() {
+            radical.push('1');
+        }
+        if !self.coefficient.is_integer() {
+            radical.push_str(&format!("/{}", self.coefficient.denominator()));
+        }
+
+        let sign = if self.coefficient.is_negative() { '-' } else { '+' };
+        if self.rational.is_zero() {
+            if self.coefficient.is_negative() {
+                write!(f, "-")?;
+            }
+            write!(f, "{radical}")
+        } else {
+            write!(f, "{:#} {sign} {radical}", self.rational)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt() {
+        let root = Surd::sqrt(Rational::from(12)).unwrap();
+        assert_eq!((Rational::from(2), 3), (root.coefficient, root.radicand));
+
+        let root = Surd::sqrt(Rational::new(1, 2)).unwrap();
+        assert_eq!((Rational::new(1, 2), 2), (root.coefficient, root.radicand));
+
+        let root = Surd::sqrt(Rational::from(-8)).unwrap();
+        assert_eq!((Rational::from(2), -2), (root.coefficient, root.radicand));
+    }
+
+    #[test]
+    fn test_quadratic_roots() {
+        let [minus, plus] =
+            Surd::quadratic_roots(Rational::ONE, Rational::ZERO, Rational::from(-2)).unwrap();
+        assert_eq!("-√2", minus.to_string());
+        assert_eq!("√2", plus.to_string());
+        assert!((plus.to_complex() - Complex::from(2.0_f64.sqrt())).norm() < 1e-12);
+
+        let [minus, plus] =
+            Surd::quadratic_roots(Rational::ONE, Rational::ONE, Rational::from(-1)).unwrap();
+        assert_eq!("-1/2 - √5/2", minus.to_string());
+        assert_eq!("-1/2 + √5/2", plus.to_string());
+
+        let [minus, plus] =
+            Surd::quadratic_roots(Rational::ONE, Rational::from(-2), Rational::from(4)).unwrap();
+        assert_eq!("1 - i√3", minus.to_string());
+        assert_eq!("1 + i√3", plus.to_string());
+        assert!((plus.to_complex() - Complex::new(1.0, 3.0_f64.sqrt())).norm() < 1e-12);
+
+        assert_eq!(
+            None,
+            Surd::quadratic_roots(Rational::ONE, Rational::ZERO, Rational::from(-4))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let surd = Surd {
+            rational: Rational::ZERO,
+            coefficient: Rational::new(3, 2),
+            radicand: 5,
+        };
+        assert_eq!("3√5/2", surd.to_string());
+        let surd = Surd {
+            rational: Rational::new(1, 4),
+            coefficient: Rational::from(-2),
+            radicand: -1,
+        };
+        assert_eq!("1/4 - 2i", surd.to_string());
+    }
+}
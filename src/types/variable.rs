@@ -9,10 +9,10 @@ impl Type for Variable {
         "#D3643B"
     }
 
-    fn into_term(&self) -> Term {
-        let mut term = Term::default();
-
-        term.identifier = Some(self.0.clone());
-        term
+    fn to_term(&self) -> Term {
+        Term {
+            identifier: Some(self.0.clone()),
+            ..Default::default()
+        }
     }
 }
\ No newline at end of file
@@ -1,5 +1,4 @@
 use crate::node::{Branch, Edges};
-use dot;
 use std::fs::File;
 
 pub fn render_graph(tree: &Branch) {
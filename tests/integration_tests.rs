@@ -4,8 +4,12 @@ mod integration_tests {
     use std::process::Command;
 
     fn compare(input: &'static str, output: &'static str) {
+        compare_with(&[], input, output);
+    }
+
+    fn compare_with(options: &[&'static str], input: &'static str, output: &'static str) {
         let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
-        cmd.arg(input).assert().success().stdout(output);
+        cmd.args(options).arg(input).assert().success().stdout(output);
     }
 
     #[test]
@@ -21,7 +25,7 @@ mod integration_tests {
 		"
         );
 
-        compare(&input, &output);
+        compare(input, output);
     }
 
     #[test]
@@ -36,7 +40,7 @@ mod integration_tests {
 		"
         );
 
-        compare(&input, &output);
+        compare(input, output);
     }
 
     #[test]
@@ -46,11 +50,270 @@ mod integration_tests {
             "
 			Reduced form: 5 * X^0 - 6 * X^1 + 0 * X^2 - 5.6 * X^3 = 0
 			Polynomial degree: 3
-			The polynomial degree is strictly greater than 2, I can't solve.
+			Discriminant is strictly negative, the real and the two complex solutions are:
+			0.615598
+			-0.307799 - 1.164324i
+			-0.307799 + 1.164324i
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn zero_discriminant() {
+        let input = "1 * X^0 + 2 * X^1 + 1 * X^2 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 1 * X^0 + 2 * X^1 + 1 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is zero, the solution is:
+			X = -1 (multiplicity 2)
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn cubic_three_real_roots() {
+        let input = "1 * X^3 - 6 * X^2 + 11 * X^1 - 6 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 6 * X^0 + 11 * X^1 - 6 * X^2 + 1 * X^3 = 0
+			Polynomial degree: 3
+			Discriminant is strictly positive, the three real solutions are:
+			1.000000
+			2.000000
+			3.000000
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn cubic_double_root() {
+        let input = "1 * X^3 - 4 * X^2 + 5 * X^1 - 2 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 2 * X^0 + 5 * X^1 - 4 * X^2 + 1 * X^3 = 0
+			Polynomial degree: 3
+			Discriminant is zero, the solutions are:
+			X = 1 (multiplicity 2)
+			2.000000
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn cubic_triple_root() {
+        let input = "1 * X^3 - 3 * X^2 + 3 * X^1 - 1 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 1 * X^0 + 3 * X^1 - 3 * X^2 + 1 * X^3 = 0
+			Polynomial degree: 3
+			Discriminant is zero, the solution is:
+			X = 1 (multiplicity 3)
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn quartic_real_roots() {
+        let input = "1 * X^4 - 6 * X^3 + 3 * X^2 + 26 * X^1 - 24 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 24 * X^0 + 26 * X^1 + 3 * X^2 - 6 * X^3 + 1 * X^4 = 0
+			Polynomial degree: 4
+			The four real solutions are:
+			-2.000000
+			1.000000
+			3.000000
+			4.000000
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn quartic_complex_and_double_roots() {
+        let input = "1 * X^4 - 2 * X^3 + 2 * X^2 - 2 * X^1 + 1 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 1 * X^0 - 2 * X^1 + 2 * X^2 - 2 * X^3 + 1 * X^4 = 0
+			Polynomial degree: 4
+			The solutions are:
+			X = 1 (multiplicity 2)
+			-1.000000i
+			1.000000i
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn quintic_falls_back_to_numeric() {
+        let input = "1 * X^5 - 15 * X^4 + 85 * X^3 - 225 * X^2 + 274 * X^1 - 120 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 120 * X^0 + 274 * X^1 - 225 * X^2 + 85 * X^3 - 15 * X^4 + 1 * X^5 = 0
+			Polynomial degree: 5
+			The solutions are:
+			1.000000
+			2.000000
+			3.000000
+			4.000000
+			5.000000
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn numeric_strategy() {
+        let input = "5 * X^0 + 2 * X^1 + 1 * X^2 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 5 * X^0 + 2 * X^1 + 1 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly negative, the two complex solutions are:
+			-1.000000 - 2.000000i
+			-1.000000 + 2.000000i
+		"
+        );
+
+        compare_with(&["--numeric", "--tolerance", "1e-12"], input, output);
+    }
+
+    #[test]
+    fn identity() {
+        let input = "5 * X^0 = 5 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 0 * X^0 = 0
+			Polynomial degree: 0
+			Each real number is a solution
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn contradiction() {
+        let input = "4 * X^0 = 3 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 1 * X^0 = 0
+			Polynomial degree: 0
+			There is no solution
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn exact_fraction_coefficients() {
+        let input = "1 / 3 * X^2 + 1 / 6 * X^1 = 1 / 2 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 0.5 * X^0 + 1/6 * X^1 + 1/3 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly positive, the two solutions are:
+			-1.500000
+			1.000000
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn exact_decimal_coefficients() {
+        let input = "0.1 * X^1 + 0.2 * X^1 = 0.3 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 0.3 * X^0 + 0.3 * X^1 = 0
+			Polynomial degree: 1
+			The solution is:
+			1.000000
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn fraction_output() {
+        let input = "5 * X^0 + 4 * X^1 = 4 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 1 * X^0 + 4 * X^1 = 0
+			Polynomial degree: 1
+			The solution is:
+			-1/4
+		"
+        );
+
+        compare_with(&["--fraction"], input, output);
+    }
+
+    #[test]
+    fn fraction_output_keeps_irrational_roots() {
+        let input = "1 * X^3 - 1 * X^2 - 2 * X^1 + 2 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 2 * X^0 - 2 * X^1 - 1 * X^2 + 1 * X^3 = 0
+			Polynomial degree: 3
+			Discriminant is strictly positive, the three real solutions are:
+			-1.414214
+			1
+			1.414214
 		"
         );
 
-        compare(&input, &output);
+        compare_with(&["--fraction"], input, output);
+    }
+
+    #[test]
+    fn exact_radical_output() {
+        let input = "1 * X^2 - 2 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 2 * X^0 + 1 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly positive, the two solutions are:
+			-√2 ≈ -1.414214
+			√2 ≈ 1.414214
+		"
+        );
+
+        compare_with(&["--exact"], input, output);
+    }
+
+    #[test]
+    fn exact_complex_radical_output() {
+        let input = "1 * X^2 - 2 * X^1 + 4 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 4 * X^0 - 2 * X^1 + 1 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly negative, the two complex solutions are:
+			1 - i√3 ≈ 1.000000 - 1.732051i
+			1 + i√3 ≈ 1.000000 + 1.732051i
+		"
+        );
+
+        compare_with(&["--exact"], input, output);
     }
 
     #[test]
@@ -66,7 +329,7 @@ mod integration_tests {
 		"
         );
 
-        compare(&input, &output);
+        compare(input, output);
     }
 
     #[test]
@@ -81,7 +344,7 @@ mod integration_tests {
 		"
         );
 
-        compare(&input, &output);
+        compare(input, output);
     }
 
     #[test]
@@ -91,10 +354,895 @@ mod integration_tests {
             "
 			Reduced form: 5 * X^0 - 6 * X^1 + 0 * X^2 - 5.6 * X^3 = 0
 			Polynomial degree: 3
-			The polynomial degree is strictly greater than 2, I can't solve.
+			Discriminant is strictly negative, the real and the two complex solutions are:
+			0.615598
+			-0.307799 - 1.164324i
+			-0.307799 + 1.164324i
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn negative_discriminant() {
+        let input = "5 * X^0 + 2 * X^1 + 1 * X^2 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 5 * X^0 + 2 * X^1 + 1 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly negative, the two complex solutions are:
+			-1.000000 - 2.000000i
+			-1.000000 + 2.000000i
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn subtracting_a_sum() {
+        let input = "1 * X^0 = 2 * X^0 + 3 * X^1";
+        let output = indoc!(
+            "
+			Reduced form: - 1 * X^0 - 3 * X^1 = 0
+			Polynomial degree: 1
+			The solution is:
+			-0.333333
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn steps_output() {
+        let input = "5 * X^0 + 4 * X^1 - 9.3 * X^2 = 1 * X^0";
+        let output = indoc!(
+            "
+			Step 1: Start from the equation: 5 * X^0 + 4 * X^1 - 9.3 * X^2 = 1 * X^0
+			Step 2: Subtract the right-hand side from both sides: 5 * X^0 + 4 * X^1 - 9.3 * X^2 - 1 * X^0 = 0
+			Step 3: Combine like terms: 4 * X^0 + 4 * X^1 - 9.3 * X^2 = 0
+			Step 4: Compute the discriminant: Δ = b² - 4ac = (4)² - 4 * (-9.3) * (4) = 164.8
+			Step 5: Δ > 0, so X = (-b ± √Δ) / 2a = (-(4) ± √Δ) / (2 * -9.3)
+			Reduced form: 4 * X^0 + 4 * X^1 - 9.3 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly positive, the two solutions are:
+			0.905239
+			-0.475131
+		"
+        );
+
+        compare_with(&["--steps"], input, output);
+    }
+
+    #[test]
+    fn repeated_roots_of_a_quintic() {
+        // (X - 2)^3 (X + 1)^2
+        let input = "1 * X^5 - 4 * X^4 + 1 * X^3 + 10 * X^2 - 4 * X^1 - 8 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 8 * X^0 - 4 * X^1 + 10 * X^2 + 1 * X^3 - 4 * X^4 + 1 * X^5 = 0
+			Polynomial degree: 5
+			The solutions are:
+			X = 2 (multiplicity 3)
+			X = -1 (multiplicity 2)
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn factor_mode() {
+        let input = "1 * X^3 - 6 * X^2 + 11 * X^1 - 6 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 6 * X^0 + 11 * X^1 - 6 * X^2 + 1 * X^3 = 0
+			Polynomial degree: 3
+			Factored form: (X - 1)(X - 2)(X - 3) = 0
+		"
+        );
+
+        compare_with(&["factor"], input, output);
+    }
+
+    #[test]
+    fn factor_mode_without_rational_roots() {
+        let input = "1 * X^4 + 4 * X^0 = 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 4 * X^0 + 1 * X^4 = 0
+			Polynomial degree: 4
+			Factored form: (X^2 - 2X + 2)(X^2 + 2X + 2) = 0
+		"
+        );
+
+        compare_with(&["factor"], input, output);
+    }
+
+    #[test]
+    fn strict_inequality() {
+        let input = "1 * X^2 - 4 * X^1 + 3 * X^0 > 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 3 * X^0 - 4 * X^1 + 1 * X^2 > 0
+			Polynomial degree: 2
+			The solution set is:
+			(-∞, 1) ∪ (3, +∞)
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn non_strict_inequality() {
+        let input = "1 * X^2 <= 2 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: - 2 * X^0 + 1 * X^2 <= 0
+			Polynomial degree: 2
+			The solution set is:
+			[-1.414214, 1.414214]
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn inequality_without_solution() {
+        let input = "1 * X^2 + 1 * X^0 < 0 * X^0";
+        let output = indoc!(
+            "
+			Reduced form: 1 * X^0 + 1 * X^2 < 0
+			Polynomial degree: 2
+			There is no solution
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn linear_system() {
+        let input = "2 * X + 1 * Y = 5; 1 * X - 1 * Y = 1";
+        let output = indoc!(
+            "
+			Augmented matrix:
+			[ 2  1 5 ]
+			[ 1 -1 1 ]
+			The system has a unique solution:
+			X = 2
+			Y = 1
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn underdetermined_linear_system() {
+        let input = "X + Y + Z = 1; X - Y = 0";
+        let output = indoc!(
+            "
+			Augmented matrix:
+			[ 1  1 1 1 ]
+			[ 1 -1 0 0 ]
+			The system has infinitely many solutions:
+			X = 1/2 - 1/2 * Z
+			Y = 1/2 - 1/2 * Z
+			Z is free
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn inconsistent_linear_system() {
+        let input = "X + Y = 1; 2 * X + 2 * Y = 3";
+        let output = indoc!(
+            "
+			Augmented matrix:
+			[ 1 1 1 ]
+			[ 2 2 3 ]
+			The system has no solution
 		"
         );
 
-        compare(&input, &output);
+        compare(input, output);
+    }
+
+    #[test]
+    fn solve_for_with_parameters() {
+        let input = "a * X^2 + b = 0";
+        let output = indoc!(
+            "
+			Reduced form: b + X^2 * a = 0
+			Polynomial degree in X: 2
+			Provided that a ≠ 0, the solution is:
+			X = ±√(-b/a)
+		"
+        );
+
+        compare_with(&["--solve-for", "X"], input, output);
+    }
+
+    #[test]
+    fn solve_for_linear_in_a_parameter() {
+        let input = "X * Y = 3";
+        let output = indoc!(
+            "
+			Reduced form: - 3 + X^1 * Y = 0
+			Polynomial degree in Y: 1
+			Provided that X ≠ 0, the solution is:
+			Y = 3/X
+		"
+        );
+
+        compare_with(&["--solve-for", "Y"], input, output);
+    }
+
+    #[test]
+    fn count_roots_in_an_interval() {
+        let input = "X^3 - 3 * X + 1 = 0";
+        let output = indoc!(
+            "
+			Reduced form: 1 - 3 * X + X^3 = 0
+			Polynomial degree: 3
+			There are 2 distinct real roots in [0, 2]:
+			X ∈ (0.344, 0.352]
+			X ∈ (1.531, 1.539]
+		"
+        );
+
+        compare_with(&["count-roots", "[0,2]", "--precision", "0.01"], input, output);
+    }
+
+    #[test]
+    fn count_roots_with_exact_and_repeated_roots() {
+        let input = "X^3 = 2 * X^2 - X";
+        let output = indoc!(
+            "
+			Reduced form: X - 2 * X^2 + X^3 = 0
+			Polynomial degree: 3
+			There are 2 distinct real roots in [-1, 1]:
+			X = 0
+			X = 1
+		"
+        );
+
+        compare_with(&["count-roots", "[-1,1]"], input, output);
+    }
+
+    #[test]
+    fn verified_roots() {
+        let input = "X^2 = 4";
+        let output = indoc!(
+            "
+			Reduced form: - 4 + X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly positive, the two solutions are:
+			-2.000000
+			2.000000
+			Verification:
+			|P(-2.000000)| = 0.000e0, Newton-polished value: -2.000000
+			|P(2.000000)| = 0.000e0, Newton-polished value: 2.000000
+		"
+        );
+
+        compare_with(&["--verify"], input, output);
+    }
+
+    #[test]
+    fn residual_beyond_tolerance_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["--verify", "--numeric", "--max-iterations", "1"])
+            .arg("X^5 - X + 1 = 0")
+            .assert()
+            .failure()
+            .code(1);
+    }
+
+    #[test]
+    fn negative_exponents() {
+        let input = "X + X^-1 = 5/2";
+        let output = indoc!(
+            "
+			Reduced form: X^0 - 2.5 * X^1 + X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly positive, the two solutions are:
+			0.500000
+			2.000000
+			X = 0 is excluded, as it makes a denominator zero
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn excluded_value_of_an_inequality() {
+        let input = "X^-2 >= 1";
+        let output = indoc!(
+            "
+			Reduced form: X^0 - 1 * X^2 >= 0
+			Polynomial degree: 2
+			The solution set is:
+			[-1, 0) ∪ (0, 1]
+			X = 0 is excluded, as it makes a denominator zero
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn rational_exponent_substitution() {
+        let input = "X + X^0.5 = 2";
+        let output = indoc!(
+            "
+			Substitution: Y = X^(1/2)
+			Reduced form: - 2 + Y^1 + Y^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly positive, the two solutions are:
+			-2.000000
+			1.000000
+			Back-substitution:
+			Y = -2.000000 is rejected, as X^(1/2) cannot be negative
+			X = 1.000000
+		"
+        );
+
+        compare_with(&["--substitute"], input, output);
+    }
+
+    #[test]
+    fn non_integer_exponent_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("X^2.5 = 1")
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("NON_INTEGER_EXPONENT_ERR"));
+    }
+
+    #[test]
+    fn three_like_terms() {
+        let input = "1 * X^1 + 1 * X^1 + 1 * X^1 - 2 * X^2 = 3 * X^0 - 2 * X^2";
+        let output = indoc! {"
+			Reduced form: - 3 * X^0 + 3 * X^1 = 0
+			Polynomial degree: 1
+			The solution is:
+			1.000000
+		"
+        };
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn expanded_products() {
+        let input = "(X + 1) * (X - 1) = 0";
+        let output = indoc! {"
+			Reduced form: - 1 + 1 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly positive, the two solutions are:
+			-1.000000
+			1.000000
+		"
+        };
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn expanded_powers() {
+        let input = "(X + 2)^2 = 9";
+        let output = indoc! {"
+			Reduced form: - 5 + 4 * X^1 + 1 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly positive, the two solutions are:
+			-5.000000
+			1.000000
+		"
+        };
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn fractional_power_of_a_sum_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("(X + 1)^0.5 = 2")
+            .assert()
+            .code(1)
+            .stderr(predicates::str::contains("SUM_EXPONENT_ERR0.5"));
+    }
+
+    #[test]
+    fn divide_with_remainder() {
+        let input = "(X^3 + 2) / (X + 1)";
+        let output = indoc! {"
+			Dividend: 2 * X^0 + 1 * X^3
+			Divisor: 1 * X^0 + 1 * X^1
+			Quotient: 1 * X^0 - 1 * X^1 + 1 * X^2
+			Remainder: 1 * X^0
+		"
+        };
+
+        compare_with(&["divide"], input, output);
+    }
+
+    #[test]
+    fn divide_by_zero_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["divide", "X / (X - X)"])
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("DIVISION_BY_ZERO_ERR"));
+    }
+
+    #[test]
+    fn exact_division_and_remainder() {
+        let input = "(X^2 - 1) / (X - 1) = (X^3 + 2) % (X + 1)";
+        let output = indoc! {"
+			Reduced form: 1 * X^1 = 0
+			Polynomial degree: 1
+			The solution is:
+			0.000000
+			X = 1 is excluded, as it makes a denominator zero
+		"
+        };
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn rational_equation() {
+        let input = "X / (X^2 - X) = 1";
+        let output = indoc! {"
+			Reduced form: 2 * X^0 - 1 * X^1 = 0
+			Polynomial degree: 1
+			The solution is:
+			2.000000
+			X = 0 is excluded, as it makes a denominator zero
+			X = 1 is excluded, as it makes a denominator zero
+		"
+        };
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn rational_inequality() {
+        let input = "1 / (X + 1) > 0";
+        let output = indoc! {"
+			Reduced form: 1 * X^0 + 1 * X^1 > 0
+			Polynomial degree: 1
+			The solution set is:
+			(-1, +∞)
+			X = -1 is excluded, as it makes a denominator zero
+		"
+        };
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn negative_power_of_a_sum() {
+        let input = "(X + 1)^-1 = 2";
+        let output = indoc! {"
+			Reduced form: - 1 * X^0 - 2 * X^1 = 0
+			Polynomial degree: 1
+			The solution is:
+			-0.500000
+			X = -1 is excluded, as it makes a denominator zero
+		"
+        };
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn gcd_and_common_roots() {
+        let output = indoc! {"
+			First: 2 * X^0 - 3 * X^1 + 1 * X^3
+			Second: 12 * X^0 - 11 * X^1 - 2 * X^2 + 1 * X^3
+			GCD: - 1 * X^0 + 1 * X^1
+			LCM: - 24 * X^0 + 34 * X^1 + 5 * X^2 - 15 * X^3 - 1 * X^4 + 1 * X^5
+			The common root is:
+			X = 1.000000
+		"
+        };
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["gcd", "X^3 - 3 * X + 2", "X^3 - 2 * X^2 - 11 * X + 12"])
+            .assert()
+            .success()
+            .stdout(output);
+    }
+
+    #[test]
+    fn gcd_of_coprime_equations() {
+        let output = indoc! {"
+			First: 1 * X^0 + 1 * X^1
+			Second: - 1 * X^0 + 1 * X^1
+			GCD: 1 * X^0
+			LCM: - 1 * X^0 + 1 * X^2
+			There is no common root
+		"
+        };
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["gcd", "X + 1 = 0", "X = 1"])
+            .assert()
+            .success()
+            .stdout(output);
+    }
+
+    #[test]
+    fn derivative_of_a_polynomial() {
+        let output = indoc! {"
+			Expression: - 5 * X^0 + 2 * X^1 + 1 * X^3
+			Derivative: 2 * X^0 + 3 * X^2
+		"
+        };
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["diff", "X^3 + 2 * X - 5"])
+            .assert()
+            .success()
+            .stdout(output);
+    }
+
+    #[test]
+    fn derivative_of_a_quotient() {
+        let output = indoc! {"
+			Expression: (1 * X^0) / (1 * X^0 + 1 * X^1)
+			Derivative: (- 1 * X^0) / (1 * X^0 + 2 * X^1 + 1 * X^2)
+		"
+        };
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["diff", "1 / (X + 1)"])
+            .assert()
+            .success()
+            .stdout(output);
+    }
+
+    #[test]
+    fn antiderivative() {
+        let output = indoc! {"
+			Expression: 1 * X^0 + 2 * X^1 + 3 * X^2
+			Antiderivative: 1 * X^1 + 1 * X^2 + 1 * X^3 + C
+		"
+        };
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["integrate", "3 * X^2 + 2 * X + 1"])
+            .assert()
+            .success()
+            .stdout(output);
+    }
+
+    #[test]
+    fn antiderivative_of_a_rational_function_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["integrate", "1 / X"])
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("NON_POLYNOMIAL_ERR"));
+    }
+
+    #[test]
+    fn imaginary_coefficient_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("2 * i * X = 1")
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("SYNTAX_ERR"));
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("X^2 = 2 * i")
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("UNEXP_TOKEN_ERR"));
+    }
+
+    #[test]
+    fn numeric_strategy_without_convergence_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["X^5 - 3 * X + 1 = 0", "--numeric", "--max-iterations", "1"])
+            .assert()
+            .code(1)
+            .stderr(predicates::str::contains("NUMERIC_CONVERGENCE_ERR"));
+    }
+
+    #[test]
+    fn coefficient_overflow_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("99999999999999999999 * X^2 * 99999999999999999999 = 1")
+            .assert()
+            .code(1)
+            .stderr(predicates::str::contains("RATIONAL_OVERFLOW_ERR"));
+    }
+
+    #[test]
+    fn repeated_irrational_roots() {
+        let input = "(Y^2 - 2)^2 = 0";
+        let output = indoc!(
+            "
+			Reduced form: 4 - 4 * Y^2 + 1 * Y^4 = 0
+			Polynomial degree: 4
+			The solutions are:
+			Y = -1.414214 (multiplicity 2)
+			Y = 1.414214 (multiplicity 2)
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn factor_with_large_coefficients() {
+        let input = "(X^2 + 1000) * (X^2 + 1001) = 0";
+        let output = indoc!(
+            "
+    		Reduced form: 1001000 + 2001 * X^2 + 1 * X^4 = 0
+    		Polynomial degree: 4
+    		Factored form: (X^2 + 1000)(X^2 + 1001) = 0
+    	"
+        );
+
+        compare_with(&["factor"], input, output);
+    }
+
+    #[test]
+    fn incomplete_factorization_is_marked() {
+        let input = "(X^6 + 1000000007) * (X^6 + 1000000009) = 0";
+        let output = indoc!(
+            "
+    		Reduced form: 1000000016000000063 + 2000000016 * X^6 + 1 * X^12 = 0
+    		Polynomial degree: 12
+    		Factored form: (X^12 + 2000000016X^6 + 1000000016000000063) = 0
+    		Factorization incomplete: a factor may still be reducible
+    	"
+        );
+
+        compare_with(&["factor"], input, output);
+    }
+
+    #[test]
+    fn system_rejects_unsupported_modes_and_flags() {
+        for (args, arg) in [
+            (&["factor"][..], "factor"),
+            (&["--steps"][..], "--steps"),
+            (&["--verify"][..], "--verify"),
+            (&["--solve-for", "X"][..], "--solve-for"),
+        ] {
+            let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+            cmd.args(args)
+                .arg("X + Y = 3; X - Y = 1")
+                .assert()
+                .code(1)
+                .stderr(predicates::str::contains(format!("SYSTEM_MODE_ERR{arg}")));
+        }
+    }
+
+    #[test]
+    fn solve_for_with_a_constant_in_the_leading_coefficient() {
+        let input = "(a + 1) * X + b = 0";
+        let output = indoc!(
+            "
+			Reduced form: b + 1 * X^1 + 1 * X^1 * a = 0
+			Polynomial degree in X: 1
+			Provided that a + 1 ≠ 0, the solution is:
+			X = -b/(a + 1)
+		"
+        );
+
+        compare_with(&["--solve-for", "X"], input, output);
+    }
+
+    #[test]
+    fn solve_for_keeps_parameters_beside_a_constant() {
+        let input = "(a+2)*X^2 - 1 = 0";
+        let output = indoc!(
+            "
+			Reduced form: - 1 + 2 * X^2 + 1 * X^2 * a = 0
+			Polynomial degree in X: 2
+			Provided that a + 2 ≠ 0, the solution is:
+			X = ±√(1/(a + 2))
+		"
+        );
+
+        compare_with(&["--solve-for", "X"], input, output);
+    }
+
+    #[test]
+    fn solve_for_a_missing_unknown_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["--solve-for", "Y", "X^2 - 4 = 0"])
+            .assert()
+            .code(1)
+            .stderr(predicates::str::contains("MISSING_UNKNOWN_ERR"));
+    }
+
+    #[test]
+    fn overflowing_power_of_a_sum_fails() {
+        for input in ["(X + 1)^200 = 0", "(X + 99999999999)^5 = 0"] {
+            let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+            cmd.arg(input)
+                .assert()
+                .code(1)
+                .stderr(predicates::str::contains("RATIONAL_OVERFLOW_ERR"));
+        }
+    }
+
+    #[test]
+    fn power_of_a_product() {
+        let input = "(2 * X)^2 = 4";
+        let output = indoc!(
+            "
+			Reduced form: - 4 + 4 * X^2 = 0
+			Polynomial degree: 2
+			Discriminant is strictly positive, the two solutions are:
+			-1.000000
+			1.000000
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn cancelled_divisor_is_still_excluded() {
+        let input = "(X^2 - 1) / (X - 1) = 2";
+        let output = indoc!(
+            "
+			Reduced form: - 1 * X^0 + 1 * X^1 = 0
+			Polynomial degree: 1
+			There is no solution
+			X = 1 is excluded, as it makes a denominator zero
+		"
+        );
+
+        compare(input, output);
+    }
+
+    #[test]
+    fn division_by_zero_fails() {
+        for input in ["X / 0 = 1", "(X + 1) / 0 = 1", "X % 0 = 1; Y = 2"] {
+            let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+            cmd.arg(input)
+                .assert()
+                .code(1)
+                .stderr(predicates::str::contains("DIVISION_BY_ZERO_ERR"));
+        }
+    }
+
+    #[test]
+    fn inexact_division_in_a_system_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("X / (X + 1) = 1; Y = 2")
+            .assert()
+            .code(1)
+            .stderr(predicates::str::contains("INEXACT_DIVISION_ERR- 1 * X^0\n"));
+    }
+
+    #[test]
+    fn degree_beyond_the_limit_fails() {
+        for input in ["X^1000000 = 1", "X^1000000 / (X - 1) = 1"] {
+            let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+            cmd.arg(input)
+                .assert()
+                .code(1)
+                .stderr(predicates::str::contains("DEGREE_LIMIT_ERR1000000"));
+        }
+    }
+
+    #[test]
+    fn derivative_of_negative_powers() {
+        let cases = [
+            (
+                "3 * X^-1",
+                indoc! {"
+			Expression: (3 * X^0) / (1 * X^1)
+			Derivative: (- 3 * X^0) / (1 * X^2)
+		"
+                },
+            ),
+            (
+                "X^-2 + X",
+                indoc! {"
+			Expression: (1 * X^0 + 1 * X^3) / (1 * X^2)
+			Derivative: (- 2 * X^0 + 1 * X^3) / (1 * X^3)
+		"
+                },
+            ),
+        ];
+
+        for (input, output) in cases {
+            let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+            cmd.args(["diff", input]).assert().success().stdout(output);
+        }
+    }
+
+    #[test]
+    fn division_by_another_unknown_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["divide", "(X^2 + 1) / (Y + 1)"])
+            .assert()
+            .code(1)
+            .stderr(predicates::str::contains("MULTIPLE_UNKNOWNS_ERRX, Y"));
+    }
+
+    #[test]
+    fn gcd_of_different_unknowns_fails() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["gcd", "X^2 - 1", "Y - 1"])
+            .assert()
+            .code(1)
+            .stderr(predicates::str::contains("MULTIPLE_UNKNOWNS_ERRX, Y"));
+    }
+
+    #[test]
+    fn count_roots_of_a_high_degree() {
+        let input = "X^6 - 3 = 0";
+        let output = indoc! {"
+			Reduced form: - 3 + X^6 = 0
+			Polynomial degree: 6
+			There are 2 distinct real roots in [-5, 5]:
+			X ∈ (-1.2009430, -1.2009239]
+			X ∈ (1.2009239, 1.2009430]
+			Refinement stopped above the precision, where the values would overflow
+		"
+        };
+        compare_with(&["count-roots", "[-5,5]"], input, output);
+    }
+
+    #[test]
+    fn cubic_with_close_roots() {
+        let input = "2.0000300001 - 3.0000300001 * X + X^3 = 0";
+        let output = indoc! {"
+			Reduced form: 2.0000300001 - 3.0000300001 * X + X^3 = 0
+			Polynomial degree: 3
+			Discriminant is strictly positive, the three real solutions are:
+			-2.000010
+			1.000000
+			1.000010
+		"
+        };
+        compare_with(&[], input, output);
+    }
+
+    #[test]
+    fn numeric_cubic_with_close_roots() {
+        let input = "2.0000300001 - 3.0000300001 * X + X^3 = 0";
+        let output = indoc! {"
+			Reduced form: 2.0000300001 - 3.0000300001 * X + X^3 = 0
+			Polynomial degree: 3
+			Discriminant is strictly positive, the three real solutions are:
+			-2.000010
+			1.000000
+			1.000010
+		"
+        };
+        compare_with(&["--numeric"], input, output);
+    }
+
+    #[test]
+    fn solve_for_without_constant() {
+        let input = "X^2 * a = 0";
+        let output = indoc! {"
+			Reduced form: X^2 * a = 0
+			Polynomial degree in X: 2
+			Provided that a ≠ 0, the solution is:
+			X = 0
+		"
+        };
+        compare_with(&["--solve-for", "X"], input, output);
     }
 }